@@ -35,6 +35,7 @@ use wasm_bindgen::prelude::*;
 
 use serde::Serialize;
 use serde_json::{Map, Number, Value};
+use std::io::{Read, Write};
 use thiserror::Error;
 
 /// Errors that can occur during canonicalization
@@ -46,6 +47,10 @@ pub enum CanonicalizeError {
     NumberOutOfRange,
     #[error("Invalid JSON structure")]
     InvalidJson,
+    #[error("I/O error: {0}")]
+    Io(String),
+    #[error("unpaired UTF-16 surrogate in a \\uXXXX escape at {path}")]
+    UnpairedSurrogate { path: String },
 }
 
 /// Canonicalize a JSON value according to RFC 8785.
@@ -72,6 +77,15 @@ pub fn canonicalize_serializable<T: Serialize>(value: &T) -> Result<Vec<u8>, Can
     canonicalize(&json_value)
 }
 
+/// Parse `json_str` as JSON and canonicalize it in one step.
+///
+/// A convenience wrapper for callers holding raw JSON text rather than an
+/// already-parsed `Value`.
+pub fn canonicalize_str(json_str: &str) -> Result<Vec<u8>, CanonicalizeError> {
+    let value: Value = serde_json::from_str(json_str).map_err(|_| CanonicalizeError::InvalidJson)?;
+    canonicalize(&value)
+}
+
 /// Internal recursive canonicalization function
 fn canonicalize_value(value: &Value) -> Result<String, CanonicalizeError> {
     match value {
@@ -86,47 +100,95 @@ fn canonicalize_value(value: &Value) -> Result<String, CanonicalizeError> {
 
 /// Canonicalize a JSON number according to RFC 8785.
 ///
-/// Rules:
-/// - No leading zeros
-/// - No positive sign
-/// - No trailing decimal zeros
-/// - No trailing decimal point
-/// - Minus zero (-0.0) is preserved as distinct from 0.0
+/// RFC 8785 pins number formatting to the ECMAScript `Number::toString`
+/// algorithm, so two conforming implementations always agree byte-for-byte
+/// on the same input value — unlike the Rust/Python default float
+/// formatters, which differ from each other and from JS in edge cases like
+/// the exponential-notation threshold. `NaN` and the infinities have no
+/// ECMAScript string form, and `-0` (which ECMAScript prints as `"0"`) would
+/// be indistinguishable from `0`; all three are rejected rather than
+/// silently coerced.
 fn canonicalize_number(n: &Number) -> Result<String, CanonicalizeError> {
     if let Some(i) = n.as_i64() {
         Ok(i.to_string())
     } else if let Some(u) = n.as_u64() {
         Ok(u.to_string())
     } else if let Some(f) = n.as_f64() {
-        // Handle special cases
-        if f.is_infinite() || f.is_nan() {
+        format_ecmascript_number(f)
+    } else {
+        Err(CanonicalizeError::NumberOutOfRange)
+    }
+}
+
+/// Format `f` per the ECMAScript `Number::toString` algorithm (ECMA-262
+/// §6.1.6.1.20), which RFC 8785 mandates for JCS number output.
+///
+/// The algorithm starts from the shortest decimal digit string `s` (length
+/// `k`) and integer `n` such that `s * 10^(n-k)` equals the value exactly —
+/// the same inputs a Ryū-style shortest-round-trip formatter produces.
+/// Rust's `{:e}` formatting already guarantees the shortest round-trip
+/// digit sequence (same property Ryū provides), so we read `s`/`n` back out
+/// of it instead of vendoring a separate shortest-float implementation:
+///
+/// - `k <= n <= 21`: the digits of `s` followed by `n - k` zeros
+/// - `0 < n <= 21`: the first `n` digits of `s`, a `.`, then the rest
+/// - `-6 < n <= 0`: `"0."` followed by `-n` zeros, then the digits of `s`
+/// - otherwise: exponential form, `d.ddd` (or just `d` when `k == 1`)
+///   followed by `e`, an explicit `+`/`-` sign, and `|n - 1|`
+fn format_ecmascript_number(f: f64) -> Result<String, CanonicalizeError> {
+    if f.is_nan() || f.is_infinite() {
+        return Err(CanonicalizeError::NumberOutOfRange);
+    }
+    if f == 0.0 {
+        // JCS has no representation for -0; ECMAScript's Number::toString
+        // collapses it to "0", which would make -0.0 and 0.0 indistinguishable
+        // in canonical output, so we reject it instead.
+        if f.is_sign_negative() {
             return Err(CanonicalizeError::NumberOutOfRange);
         }
+        return Ok("0".to_string());
+    }
 
-        // Format with 17 significant digits then strip trailing zeros.
-        // This value fell through the integer branches, so it is stored as a
-        // float in serde_json (e.g. 0.0, -0.0, 0.125).  Python's json.dumps
-        // preserves the decimal point for such values (0.0 → "0.0"), so we
-        // keep at least one digit after the decimal to stay byte-for-byte
-        // compatible with the Python canonical-JSON implementation.
-        let mut s = format!("{:.17}", f);
+    let negative = f.is_sign_negative();
+    let abs = f.abs();
 
-        if s.contains('.') {
-            while s.ends_with('0') {
-                s.pop();
-            }
-            // Keep the trailing '.' so we can append '0' below — don't strip it.
-        }
+    let sci = format!("{:e}", abs);
+    let (mantissa, exp_str) = sci.split_once('e').expect("Rust's {:e} output always contains an 'e'");
+    let exp: i64 = exp_str.parse().expect("Rust's {:e} exponent is always a valid integer");
 
-        // Ensure there is always at least one decimal digit (e.g. "0." → "0.0").
-        if s.ends_with('.') {
-            s.push('0');
-        }
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i64;
+    let n = exp + 1;
 
-        Ok(s)
+    let mut out = String::new();
+    if negative {
+        out.push('-');
+    }
+
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(std::iter::repeat('0').take((n - k) as usize));
+    } else if n > 0 && n <= 21 {
+        out.push_str(&digits[..n as usize]);
+        out.push('.');
+        out.push_str(&digits[n as usize..]);
+    } else if n > -6 && n <= 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat('0').take((-n) as usize));
+        out.push_str(&digits);
     } else {
-        Err(CanonicalizeError::NumberOutOfRange)
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        let e = n - 1;
+        out.push(if e >= 0 { '+' } else { '-' });
+        out.push_str(&e.abs().to_string());
     }
+
+    Ok(out)
 }
 
 /// Canonicalize a JSON string according to RFC 8785.
@@ -176,16 +238,26 @@ fn canonicalize_array(arr: &[Value]) -> Result<String, CanonicalizeError> {
     Ok(result)
 }
 
+/// Compare two object keys the way RFC 8785 requires: by the lexicographic
+/// order of their UTF-16 code units, not by Unicode scalar value. The two
+/// orders agree everywhere except supplementary-plane characters, where a
+/// scalar-value comparison (e.g. plain `str::cmp`) sorts a surrogate-pair
+/// character (U+10000 and above) *after* BMP characters in the U+E000..=U+FFFF
+/// range, while their UTF-16 surrogate encodings (U+D800..=U+DBFF) actually
+/// sort *before* them.
+fn utf16_key_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    a.encode_utf16().cmp(b.encode_utf16())
+}
+
 /// Canonicalize a JSON object.
 ///
-/// Keys are sorted lexicographically by Unicode code point.
+/// Keys are sorted by UTF-16 code unit order, per RFC 8785.
 fn canonicalize_object(obj: &Map<String, Value>) -> Result<String, CanonicalizeError> {
     let mut result = String::from("{");
-    
-    // Sort keys lexicographically by Unicode code point
+
     let mut keys: Vec<&String> = obj.keys().collect();
-    keys.sort_by(|a, b| a.as_str().cmp(b.as_str()));
-    
+    keys.sort_by(|a, b| utf16_key_cmp(a, b));
+
     for (i, key) in keys.iter().enumerate() {
         if i > 0 {
             result.push(',');
@@ -226,6 +298,555 @@ pub fn canonical_hash_hex(value: &Value) -> Result<String, CanonicalizeError> {
     Ok(hex::encode(hash))
 }
 
+// ---------------------------------------------------------------------------
+// Streaming canonicalization
+// ---------------------------------------------------------------------------
+
+/// Canonicalize JSON read incrementally from `input`, writing canonical
+/// bytes directly to `output` as they're produced, without materializing
+/// the whole document as a `serde_json::Value` tree first.
+///
+/// Object key sorting is the one place a streaming canonicalizer can't
+/// avoid buffering: an object's members can't be emitted until all of them
+/// have arrived, so each open object buffers only its own direct members
+/// (each member's key plus its already-canonicalized value bytes), sorts
+/// those by UTF-16 code unit order, then flushes. A nested object recurses
+/// into its own local buffer rather than sharing its parent's, so peak
+/// memory is bounded by the single deepest/widest object in the document,
+/// not the document as a whole. Arrays and scalar values are written
+/// straight through since they need no reordering.
+pub fn canonicalize_stream<R: Read, W: Write>(input: R, mut output: W) -> Result<(), CanonicalizeError> {
+    let mut reader = ByteReader::new(input);
+    stream_value(&mut reader, &mut output)?;
+    reader.skip_whitespace()?;
+    if reader.peek()?.is_some() {
+        return Err(CanonicalizeError::InvalidJson);
+    }
+    Ok(())
+}
+
+struct ByteReader<R: Read> {
+    inner: R,
+    peeked: Option<u8>,
+}
+
+impl<R: Read> ByteReader<R> {
+    fn new(inner: R) -> Self {
+        ByteReader { inner, peeked: None }
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, CanonicalizeError> {
+        if self.peeked.is_none() {
+            self.peeked = self.read_byte()?;
+        }
+        Ok(self.peeked)
+    }
+
+    fn next(&mut self) -> Result<Option<u8>, CanonicalizeError> {
+        if let Some(b) = self.peeked.take() {
+            return Ok(Some(b));
+        }
+        self.read_byte()
+    }
+
+    fn read_byte(&mut self) -> Result<Option<u8>, CanonicalizeError> {
+        let mut buf = [0u8; 1];
+        loop {
+            return match self.inner.read(&mut buf) {
+                Ok(0) => Ok(None),
+                Ok(_) => Ok(Some(buf[0])),
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => Err(CanonicalizeError::Io(e.to_string())),
+            };
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), CanonicalizeError> {
+        while let Some(b) = self.peek()? {
+            if b == b' ' || b == b'\t' || b == b'\n' || b == b'\r' {
+                self.next()?;
+            } else {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), CanonicalizeError> {
+        match self.next()? {
+            Some(b) if b == byte => Ok(()),
+            _ => Err(CanonicalizeError::InvalidJson),
+        }
+    }
+
+    fn expect_literal(&mut self, literal: &[u8]) -> Result<(), CanonicalizeError> {
+        for &b in literal {
+            self.expect(b)?;
+        }
+        Ok(())
+    }
+}
+
+fn stream_value<R: Read, W: Write>(reader: &mut ByteReader<R>, writer: &mut W) -> Result<(), CanonicalizeError> {
+    reader.skip_whitespace()?;
+    match reader.peek()?.ok_or(CanonicalizeError::InvalidJson)? {
+        b'n' => {
+            reader.expect_literal(b"null")?;
+            writer.write_all(b"null").map_err(io_err)
+        }
+        b't' => {
+            reader.expect_literal(b"true")?;
+            writer.write_all(b"true").map_err(io_err)
+        }
+        b'f' => {
+            reader.expect_literal(b"false")?;
+            writer.write_all(b"false").map_err(io_err)
+        }
+        b'"' => {
+            let s = read_json_string(reader)?;
+            writer.write_all(canonicalize_string(&s).as_bytes()).map_err(io_err)
+        }
+        b'[' => stream_array(reader, writer),
+        b'{' => stream_object(reader, writer),
+        b'-' | b'0'..=b'9' => {
+            let tok = read_number_token(reader)?;
+            let n: Number = serde_json::from_str(&tok).map_err(|_| CanonicalizeError::InvalidJson)?;
+            writer.write_all(canonicalize_number(&n)?.as_bytes()).map_err(io_err)
+        }
+        _ => Err(CanonicalizeError::InvalidJson),
+    }
+}
+
+fn stream_array<R: Read, W: Write>(reader: &mut ByteReader<R>, writer: &mut W) -> Result<(), CanonicalizeError> {
+    reader.expect(b'[')?;
+    writer.write_all(b"[").map_err(io_err)?;
+
+    reader.skip_whitespace()?;
+    let mut first = true;
+    while reader.peek()? != Some(b']') {
+        if !first {
+            reader.skip_whitespace()?;
+            reader.expect(b',')?;
+            writer.write_all(b",").map_err(io_err)?;
+        }
+        stream_value(reader, writer)?;
+        reader.skip_whitespace()?;
+        first = false;
+    }
+    reader.expect(b']')?;
+    writer.write_all(b"]").map_err(io_err)
+}
+
+fn stream_object<R: Read, W: Write>(reader: &mut ByteReader<R>, writer: &mut W) -> Result<(), CanonicalizeError> {
+    reader.expect(b'{')?;
+
+    // Buffer only this object's own direct members; a nested object's
+    // members are buffered separately inside its own recursive call.
+    let mut members: Vec<(String, Vec<u8>)> = Vec::new();
+
+    reader.skip_whitespace()?;
+    let mut first = true;
+    while reader.peek()? != Some(b'}') {
+        if !first {
+            reader.skip_whitespace()?;
+            reader.expect(b',')?;
+            reader.skip_whitespace()?;
+        }
+        let key = read_json_string(reader)?;
+        reader.skip_whitespace()?;
+        reader.expect(b':')?;
+
+        let mut value_bytes = Vec::new();
+        stream_value(reader, &mut value_bytes)?;
+        members.push((key, value_bytes));
+
+        reader.skip_whitespace()?;
+        first = false;
+    }
+    reader.expect(b'}')?;
+
+    members.sort_by(|(a, _), (b, _)| utf16_key_cmp(a, b));
+
+    writer.write_all(b"{").map_err(io_err)?;
+    for (i, (key, value_bytes)) in members.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",").map_err(io_err)?;
+        }
+        writer.write_all(canonicalize_string(key).as_bytes()).map_err(io_err)?;
+        writer.write_all(b":").map_err(io_err)?;
+        writer.write_all(value_bytes).map_err(io_err)?;
+    }
+    writer.write_all(b"}").map_err(io_err)
+}
+
+/// Read a raw (unparsed) number token — digits, sign, decimal point, and
+/// exponent — so it can be handed to `serde_json::Number`'s own parser and
+/// then formatted by the same `canonicalize_number` the tree-based path
+/// uses.
+fn read_number_token<R: Read>(reader: &mut ByteReader<R>) -> Result<String, CanonicalizeError> {
+    let mut tok = String::new();
+    while let Some(b) = reader.peek()? {
+        match b {
+            b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9' => {
+                tok.push(b as char);
+                reader.next()?;
+            }
+            _ => break,
+        }
+    }
+    if tok.is_empty() {
+        return Err(CanonicalizeError::InvalidJson);
+    }
+    Ok(tok)
+}
+
+/// Read a JSON string literal, decoding escape sequences (including
+/// `\uXXXX` surrogate pairs) into a `String`.
+fn read_json_string<R: Read>(reader: &mut ByteReader<R>) -> Result<String, CanonicalizeError> {
+    reader.expect(b'"')?;
+    let mut bytes = Vec::new();
+    loop {
+        match reader.next()?.ok_or(CanonicalizeError::InvalidJson)? {
+            b'"' => break,
+            b'\\' => match reader.next()?.ok_or(CanonicalizeError::InvalidJson)? {
+                b'"' => bytes.push(b'"'),
+                b'\\' => bytes.push(b'\\'),
+                b'/' => bytes.push(b'/'),
+                b'n' => bytes.push(b'\n'),
+                b'r' => bytes.push(b'\r'),
+                b't' => bytes.push(b'\t'),
+                b'b' => bytes.push(0x08),
+                b'f' => bytes.push(0x0C),
+                b'u' => {
+                    let hi = read_hex4(reader)?;
+                    let code_point = if (0xD800..=0xDBFF).contains(&hi) {
+                        reader.expect(b'\\')?;
+                        reader.expect(b'u')?;
+                        let lo = read_hex4(reader)?;
+                        if !(0xDC00..=0xDFFF).contains(&lo) {
+                            return Err(CanonicalizeError::InvalidJson);
+                        }
+                        0x10000 + (((hi - 0xD800) as u32) << 10) + (lo - 0xDC00) as u32
+                    } else {
+                        hi as u32
+                    };
+                    let c = char::from_u32(code_point).ok_or(CanonicalizeError::InvalidJson)?;
+                    let mut buf = [0u8; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                _ => return Err(CanonicalizeError::InvalidJson),
+            },
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| CanonicalizeError::InvalidUtf8)
+}
+
+fn read_hex4<R: Read>(reader: &mut ByteReader<R>) -> Result<u16, CanonicalizeError> {
+    let mut v: u16 = 0;
+    for _ in 0..4 {
+        let b = reader.next()?.ok_or(CanonicalizeError::InvalidJson)?;
+        let digit = (b as char).to_digit(16).ok_or(CanonicalizeError::InvalidJson)? as u16;
+        v = v * 16 + digit;
+    }
+    Ok(v)
+}
+
+fn io_err(e: std::io::Error) -> CanonicalizeError {
+    CanonicalizeError::Io(e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Strict surrogate validation
+// ---------------------------------------------------------------------------
+
+/// Canonicalize `json_str`, rejecting any unpaired (lone) high or low
+/// UTF-16 surrogate found in a `\uXXXX` string escape — in either an object
+/// key or a value — with a `CanonicalizeError::UnpairedSurrogate` that says
+/// where it was found.
+///
+/// `serde_json::Value` can't hold an unpaired surrogate at all (Rust's
+/// `char`/`str` only admit valid scalar values), so `canonicalize_str` can
+/// only report such input as a generic `InvalidJson` from the parser, with
+/// no location. Reporting the location requires
+/// parsing the raw text ourselves — this reuses the same byte-level reader
+/// `canonicalize_stream` does, but threads an RFC 6901 JSON-Pointer `path`
+/// through the recursion so a failure names the offending key or value.
+pub fn canonicalize_str_strict(json_str: &str) -> Result<Vec<u8>, CanonicalizeError> {
+    let mut reader = ByteReader::new(json_str.as_bytes());
+    let mut out = Vec::new();
+    stream_value_strict(&mut reader, &mut out, "")?;
+    reader.skip_whitespace()?;
+    if reader.peek()?.is_some() {
+        return Err(CanonicalizeError::InvalidJson);
+    }
+    Ok(out)
+}
+
+fn stream_value_strict<R: Read, W: Write>(
+    reader: &mut ByteReader<R>,
+    writer: &mut W,
+    path: &str,
+) -> Result<(), CanonicalizeError> {
+    reader.skip_whitespace()?;
+    match reader.peek()?.ok_or(CanonicalizeError::InvalidJson)? {
+        b'n' => {
+            reader.expect_literal(b"null")?;
+            writer.write_all(b"null").map_err(io_err)
+        }
+        b't' => {
+            reader.expect_literal(b"true")?;
+            writer.write_all(b"true").map_err(io_err)
+        }
+        b'f' => {
+            reader.expect_literal(b"false")?;
+            writer.write_all(b"false").map_err(io_err)
+        }
+        b'"' => {
+            let s = read_json_string_strict(reader, path)?;
+            writer.write_all(canonicalize_string(&s).as_bytes()).map_err(io_err)
+        }
+        b'[' => stream_array_strict(reader, writer, path),
+        b'{' => stream_object_strict(reader, writer, path),
+        b'-' | b'0'..=b'9' => {
+            let tok = read_number_token(reader)?;
+            let n: Number = serde_json::from_str(&tok).map_err(|_| CanonicalizeError::InvalidJson)?;
+            writer.write_all(canonicalize_number(&n)?.as_bytes()).map_err(io_err)
+        }
+        _ => Err(CanonicalizeError::InvalidJson),
+    }
+}
+
+fn stream_array_strict<R: Read, W: Write>(
+    reader: &mut ByteReader<R>,
+    writer: &mut W,
+    path: &str,
+) -> Result<(), CanonicalizeError> {
+    reader.expect(b'[')?;
+    writer.write_all(b"[").map_err(io_err)?;
+
+    reader.skip_whitespace()?;
+    let mut index = 0usize;
+    let mut first = true;
+    while reader.peek()? != Some(b']') {
+        if !first {
+            reader.skip_whitespace()?;
+            reader.expect(b',')?;
+            writer.write_all(b",").map_err(io_err)?;
+        }
+        let element_path = format!("{path}/{index}");
+        stream_value_strict(reader, writer, &element_path)?;
+        reader.skip_whitespace()?;
+        index += 1;
+        first = false;
+    }
+    reader.expect(b']')?;
+    writer.write_all(b"]").map_err(io_err)
+}
+
+fn stream_object_strict<R: Read, W: Write>(
+    reader: &mut ByteReader<R>,
+    writer: &mut W,
+    path: &str,
+) -> Result<(), CanonicalizeError> {
+    reader.expect(b'{')?;
+    let mut members: Vec<(String, Vec<u8>)> = Vec::new();
+
+    reader.skip_whitespace()?;
+    let mut first = true;
+    while reader.peek()? != Some(b'}') {
+        if !first {
+            reader.skip_whitespace()?;
+            reader.expect(b',')?;
+            reader.skip_whitespace()?;
+        }
+        let key_path = format!("{path}/<key>");
+        let key = read_json_string_strict(reader, &key_path)?;
+        reader.skip_whitespace()?;
+        reader.expect(b':')?;
+
+        let member_path = format!("{path}/{}", escape_pointer_segment(&key));
+        let mut value_bytes = Vec::new();
+        stream_value_strict(reader, &mut value_bytes, &member_path)?;
+        members.push((key, value_bytes));
+
+        reader.skip_whitespace()?;
+        first = false;
+    }
+    reader.expect(b'}')?;
+
+    members.sort_by(|(a, _), (b, _)| utf16_key_cmp(a, b));
+
+    writer.write_all(b"{").map_err(io_err)?;
+    for (i, (key, value_bytes)) in members.iter().enumerate() {
+        if i > 0 {
+            writer.write_all(b",").map_err(io_err)?;
+        }
+        writer.write_all(canonicalize_string(key).as_bytes()).map_err(io_err)?;
+        writer.write_all(b":").map_err(io_err)?;
+        writer.write_all(value_bytes).map_err(io_err)?;
+    }
+    writer.write_all(b"}").map_err(io_err)
+}
+
+/// Escape `~` and `/` in a JSON-Pointer (RFC 6901) path segment.
+fn escape_pointer_segment(s: &str) -> String {
+    s.replace('~', "~0").replace('/', "~1")
+}
+
+/// Like `read_json_string`, but reports any unpaired high or low UTF-16
+/// surrogate in a `\uXXXX` escape as `UnpairedSurrogate` at `path`, where
+/// `read_json_string` returns a bare `InvalidJson`.
+///
+/// A high surrogate (`0xD800..=0xDBFF`) must be immediately followed by a
+/// `\uXXXX` escape whose value is a low surrogate (`0xDC00..=0xDFFF`); a
+/// low surrogate may never appear first. Both a high surrogate at the very
+/// end of the string and a high surrogate followed by anything other than
+/// a valid low surrogate are unpaired.
+fn read_json_string_strict<R: Read>(reader: &mut ByteReader<R>, path: &str) -> Result<String, CanonicalizeError> {
+    reader.expect(b'"')?;
+    let mut bytes = Vec::new();
+    loop {
+        match reader.next()?.ok_or(CanonicalizeError::InvalidJson)? {
+            b'"' => break,
+            b'\\' => match reader.next()?.ok_or(CanonicalizeError::InvalidJson)? {
+                b'"' => bytes.push(b'"'),
+                b'\\' => bytes.push(b'\\'),
+                b'/' => bytes.push(b'/'),
+                b'n' => bytes.push(b'\n'),
+                b'r' => bytes.push(b'\r'),
+                b't' => bytes.push(b'\t'),
+                b'b' => bytes.push(0x08),
+                b'f' => bytes.push(0x0C),
+                b'u' => {
+                    let hi = read_hex4(reader)?;
+                    if (0xDC00..=0xDFFF).contains(&hi) {
+                        return Err(CanonicalizeError::UnpairedSurrogate { path: path.to_string() });
+                    }
+                    let code_point = if (0xD800..=0xDBFF).contains(&hi) {
+                        if reader.peek()? != Some(b'\\') {
+                            return Err(CanonicalizeError::UnpairedSurrogate { path: path.to_string() });
+                        }
+                        reader.next()?;
+                        if reader.peek()? != Some(b'u') {
+                            return Err(CanonicalizeError::UnpairedSurrogate { path: path.to_string() });
+                        }
+                        reader.next()?;
+                        let lo = read_hex4(reader)?;
+                        if !(0xDC00..=0xDFFF).contains(&lo) {
+                            return Err(CanonicalizeError::UnpairedSurrogate { path: path.to_string() });
+                        }
+                        0x10000 + (((hi - 0xD800) as u32) << 10) + (lo - 0xDC00) as u32
+                    } else {
+                        hi as u32
+                    };
+                    let c = char::from_u32(code_point).ok_or(CanonicalizeError::InvalidJson)?;
+                    let mut buf = [0u8; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                _ => return Err(CanonicalizeError::InvalidJson),
+            },
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| CanonicalizeError::InvalidUtf8)
+}
+
+// ---------------------------------------------------------------------------
+// CESU-8 input decoding
+// ---------------------------------------------------------------------------
+
+/// Parse CESU-8-encoded JSON text and canonicalize it.
+///
+/// CESU-8 (emitted by some JVM-derived serializers and databases) encodes
+/// each half of a supplementary-plane character's UTF-16 surrogate pair as
+/// its own independent 3-byte sequence, rather than UTF-8's single 4-byte
+/// sequence for the combined scalar value. This decodes `bytes` as CESU-8,
+/// recombining each such pair back into the real scalar value, then hands
+/// the result to `canonicalize_str` so the output is ordinary UTF-8 JCS —
+/// identical to what a native UTF-8 producer of the same logical document
+/// would have emitted. A 3-byte sequence that decodes to a surrogate with
+/// no immediately-following mate (a lone high surrogate, or any low
+/// surrogate appearing first) is malformed CESU-8 and is rejected rather
+/// than silently passed through.
+pub fn canonicalize_cesu8(bytes: &[u8]) -> Result<Vec<u8>, CanonicalizeError> {
+    let json_str = decode_cesu8(bytes)?;
+    canonicalize_str(&json_str)
+}
+
+/// Decode one UTF-8-style multi-byte sequence of exactly `len` bytes
+/// starting at `start`, returning its raw code point value (which, for a
+/// CESU-8 3-byte sequence, may be an otherwise-invalid surrogate value)
+/// and `len` itself for the caller's convenience.
+fn decode_utf8_seq(bytes: &[u8], start: usize, len: usize) -> Result<(u32, usize), CanonicalizeError> {
+    if start + len > bytes.len() {
+        return Err(CanonicalizeError::InvalidUtf8);
+    }
+    let mut cp = match len {
+        2 => (bytes[start] & 0x1F) as u32,
+        3 => (bytes[start] & 0x0F) as u32,
+        4 => (bytes[start] & 0x07) as u32,
+        _ => unreachable!("decode_utf8_seq is only called with len in 2..=4"),
+    };
+    for k in 1..len {
+        let b = bytes[start + k];
+        if b & 0xC0 != 0x80 {
+            return Err(CanonicalizeError::InvalidUtf8);
+        }
+        cp = (cp << 6) | (b & 0x3F) as u32;
+    }
+    Ok((cp, len))
+}
+
+fn decode_cesu8(bytes: &[u8]) -> Result<String, CanonicalizeError> {
+    let mut out = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let b0 = bytes[i];
+        if b0 < 0x80 {
+            out.push(b0 as char);
+            i += 1;
+        } else if b0 & 0xE0 == 0xC0 {
+            let (cp, len) = decode_utf8_seq(bytes, i, 2)?;
+            out.push(char::from_u32(cp).ok_or(CanonicalizeError::InvalidUtf8)?);
+            i += len;
+        } else if b0 & 0xF0 == 0xE0 {
+            let (cp, len) = decode_utf8_seq(bytes, i, 3)?;
+            if (0xD800..=0xDBFF).contains(&cp) {
+                // High surrogate half: must be immediately followed by its
+                // CESU-8-encoded low surrogate mate.
+                let j = i + len;
+                if j >= bytes.len() || bytes[j] & 0xF0 != 0xE0 {
+                    return Err(CanonicalizeError::InvalidUtf8);
+                }
+                let (lo, lo_len) = decode_utf8_seq(bytes, j, 3)?;
+                if !(0xDC00..=0xDFFF).contains(&lo) {
+                    return Err(CanonicalizeError::InvalidUtf8);
+                }
+                let combined = 0x10000 + ((cp - 0xD800) << 10) + (lo - 0xDC00);
+                out.push(char::from_u32(combined).ok_or(CanonicalizeError::InvalidUtf8)?);
+                i = j + lo_len;
+            } else if (0xDC00..=0xDFFF).contains(&cp) {
+                // A low surrogate can never lead a pair.
+                return Err(CanonicalizeError::InvalidUtf8);
+            } else {
+                out.push(char::from_u32(cp).ok_or(CanonicalizeError::InvalidUtf8)?);
+                i += len;
+            }
+        } else if b0 & 0xF8 == 0xF0 {
+            // A plain 4-byte UTF-8 sequence for a supplementary character
+            // is not how CESU-8 encodes one, but accepting it too costs
+            // nothing and tolerates input that mixes the two.
+            let (cp, len) = decode_utf8_seq(bytes, i, 4)?;
+            out.push(char::from_u32(cp).ok_or(CanonicalizeError::InvalidUtf8)?);
+            i += len;
+        } else {
+            return Err(CanonicalizeError::InvalidUtf8);
+        }
+    }
+    Ok(out)
+}
+
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub fn canonicalize_js(value: &JsValue) -> Result<Vec<u8>, JsValue> {
@@ -318,11 +939,36 @@ mod tests {
             "frac": 0.125,
             "big": 1000000.5
         });
-        
+
         let result = canonical_to_string(&value).unwrap();
         assert_eq!(result, r#"{"big":1000000.5,"frac":0.125,"int":10,"neg_int":-7}"#);
     }
 
+    #[test]
+    fn test_ecmascript_number_formatting_boundaries() {
+        assert_eq!(canonicalize_number(&Number::from_f64(1e20).unwrap()).unwrap(), "100000000000000000000");
+        assert_eq!(canonicalize_number(&Number::from_f64(1e21).unwrap()).unwrap(), "1e+21");
+        assert_eq!(canonicalize_number(&Number::from_f64(1e30).unwrap()).unwrap(), "1e+30");
+        assert_eq!(canonicalize_number(&Number::from_f64(1e-6).unwrap()).unwrap(), "0.000001");
+        assert_eq!(canonicalize_number(&Number::from_f64(1e-7).unwrap()).unwrap(), "1e-7");
+        assert_eq!(canonicalize_number(&Number::from_f64(-123.456).unwrap()).unwrap(), "-123.456");
+        assert_eq!(canonicalize_number(&Number::from_f64(1.23).unwrap()).unwrap(), "1.23");
+    }
+
+    #[test]
+    fn test_negative_zero_and_non_finite_numbers_are_rejected() {
+        // `serde_json::Number` itself can't hold NaN/infinity, so those are
+        // exercised directly against the float formatter; -0.0 is finite and
+        // does round-trip through `Number`, so it's checked both ways.
+        assert_eq!(
+            canonicalize_number(&Number::from_f64(-0.0).unwrap()),
+            Err(CanonicalizeError::NumberOutOfRange)
+        );
+        assert_eq!(format_ecmascript_number(-0.0), Err(CanonicalizeError::NumberOutOfRange));
+        assert_eq!(format_ecmascript_number(f64::NAN), Err(CanonicalizeError::NumberOutOfRange));
+        assert_eq!(format_ecmascript_number(f64::INFINITY), Err(CanonicalizeError::NumberOutOfRange));
+    }
+
     #[test]
     fn test_empty_containers() {
         let value = json!({
@@ -345,4 +991,123 @@ mod tests {
         // Same logical object should produce same hash
         assert_eq!(hash1, hash2);
     }
+
+    fn stream_canonicalize_str(input: &str) -> String {
+        let mut out = Vec::new();
+        canonicalize_stream(input.as_bytes(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_stream_matches_tree_canonicalization() {
+        let inputs = [
+            r#"{"z":null,"a":true,"b":[1,2,3]}"#,
+            r#"{"a":[1,{"b":2},[3,4]],"c":{"d":{"e":5}}}"#,
+            r#"{"n":1.2300,"m":1E+30}"#,
+            r#"{"s":"quote: \", backslash: \\, tab: \t, newline: \n"}"#,
+            r#"{"empty_obj":{},"empty_arr":[]}"#,
+        ];
+
+        for input in inputs {
+            let value: Value = serde_json::from_str(input).unwrap();
+            let expected = canonical_to_string(&value).unwrap();
+            assert_eq!(stream_canonicalize_str(input), expected, "mismatch for {input}");
+        }
+    }
+
+    #[test]
+    fn test_stream_sorts_object_keys_by_utf16_code_unit() {
+        let input = r#"{"�":2,"😀":1,"a":0}"#;
+        assert_eq!(stream_canonicalize_str(input), r#"{"a":0,"😀":1,"�":2}"#);
+    }
+
+    #[test]
+    fn test_stream_rejects_trailing_garbage() {
+        let mut out = Vec::new();
+        let err = canonicalize_stream("{}garbage".as_bytes(), &mut out).unwrap_err();
+        assert_eq!(err, CanonicalizeError::InvalidJson);
+    }
+
+    #[test]
+    fn test_strict_accepts_valid_surrogate_pair() {
+        let out = canonicalize_str_strict(r#"{"a":"😀"}"#).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "{\"a\":\"\u{1F600}\"}");
+    }
+
+    #[test]
+    fn test_strict_rejects_lone_high_surrogate_at_end_of_string() {
+        let err = canonicalize_str_strict(r#"{"a":"\ud800"}"#).unwrap_err();
+        assert_eq!(err, CanonicalizeError::UnpairedSurrogate { path: "/a".to_string() });
+    }
+
+    #[test]
+    fn test_strict_rejects_high_surrogate_not_followed_by_escape() {
+        let err = canonicalize_str_strict(r#"{"a":"\ud800x"}"#).unwrap_err();
+        assert_eq!(err, CanonicalizeError::UnpairedSurrogate { path: "/a".to_string() });
+    }
+
+    #[test]
+    fn test_strict_rejects_lone_low_surrogate_as_leading_escape() {
+        let err = canonicalize_str_strict(r#"{"a":"\udc00"}"#).unwrap_err();
+        assert_eq!(err, CanonicalizeError::UnpairedSurrogate { path: "/a".to_string() });
+    }
+
+    #[test]
+    fn test_strict_rejects_high_surrogate_followed_by_non_low_surrogate() {
+        let err = canonicalize_str_strict(r#"{"a":"\ud800\ud800"}"#).unwrap_err();
+        assert_eq!(err, CanonicalizeError::UnpairedSurrogate { path: "/a".to_string() });
+    }
+
+    #[test]
+    fn test_strict_accepts_surrogate_range_boundaries() {
+        // 0xD7FF (just below the high-surrogate range) and 0xE000 (just
+        // above the low-surrogate range) are ordinary BMP characters, not
+        // surrogates, so the off-by-one boundary must not reject them.
+        assert!(canonicalize_str_strict(r#"{"a":"\ud7ff"}"#).is_ok());
+        assert!(canonicalize_str_strict(r#"{"a":"\ue000"}"#).is_ok());
+    }
+
+    #[test]
+    fn test_strict_reports_path_of_nested_offending_value() {
+        let err = canonicalize_str_strict(r#"{"a":[0,{"b":"\ud800"}]}"#).unwrap_err();
+        assert_eq!(err, CanonicalizeError::UnpairedSurrogate { path: "/a/1/b".to_string() });
+    }
+
+    #[test]
+    fn test_cesu8_recombines_surrogate_pair() {
+        // U+1F600 (😀), CESU-8-encoded as its two surrogate halves
+        // (0xD83D, 0xDE00), each its own 3-byte sequence.
+        let mut bytes = br#"{"a":""#.to_vec();
+        bytes.extend_from_slice(&[0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]);
+        bytes.extend_from_slice(br#""}"#);
+
+        let out = canonicalize_cesu8(&bytes).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "{\"a\":\"\u{1F600}\"}");
+    }
+
+    #[test]
+    fn test_cesu8_passes_through_ascii_and_bmp() {
+        // "café" as plain UTF-8 bytes (é = 0xC3 0xA9), identical in CESU-8.
+        let bytes = b"{\"b\":1,\"a\":\"caf\xc3\xa9\"}";
+        let out = canonicalize_cesu8(bytes).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "{\"a\":\"caf\u{e9}\",\"b\":1}");
+    }
+
+    #[test]
+    fn test_cesu8_rejects_lone_high_surrogate() {
+        let mut bytes = br#"{"a":""#.to_vec();
+        bytes.extend_from_slice(&[0xED, 0xA0, 0xBD]); // high surrogate half only
+        bytes.extend_from_slice(br#""}"#);
+
+        assert_eq!(canonicalize_cesu8(&bytes), Err(CanonicalizeError::InvalidUtf8));
+    }
+
+    #[test]
+    fn test_cesu8_rejects_lone_low_surrogate_as_leading() {
+        let mut bytes = br#"{"a":""#.to_vec();
+        bytes.extend_from_slice(&[0xED, 0xB8, 0x80]); // low surrogate half only
+        bytes.extend_from_slice(br#""}"#);
+
+        assert_eq!(canonicalize_cesu8(&bytes), Err(CanonicalizeError::InvalidUtf8));
+    }
 }
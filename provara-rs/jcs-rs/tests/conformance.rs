@@ -36,11 +36,11 @@ fn rfc_examples_and_edges() {
     let out = canonicalize_str(r#"{"nfc":"\u00e9","nfd":"e\u0301"}"#).unwrap();
     assert_eq!(String::from_utf8(out).unwrap(), r#"{"nfc":"Ã©","nfd":"eÌ"}"#);
 
-    // Number normalization behavior expected by current suite
+    // Number normalization: ECMAScript Number::toString is now pinned down
+    // exactly, so implementations can no longer disagree on the exponential
+    // threshold or sign.
     let out = canonicalize_str(r#"{"n":1.2300,"m":1E+30}"#).unwrap();
-    let s = std::str::from_utf8(&out).unwrap();
-    assert!(s.contains("1.23"));
-    assert!(s.contains("1e30") || s.contains("1e+30") || s.contains("1000000000000000000000000000000"));
+    assert_eq!(String::from_utf8(out).unwrap(), r#"{"m":1e+30,"n":1.23}"#);
 
     // UTF-16 sort behavior check
     let out = canonicalize_str(r#"{"\ud83d\ude00":1,"\ufffd":2}"#).unwrap();
@@ -1,7 +1,12 @@
 use base64::Engine as _;
+use bip39::Mnemonic;
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use hmac::{Hmac, Mac};
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::Pkcs1v15Sign;
 use serde_json::{json, Value};
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
 use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
@@ -19,6 +24,293 @@ fn key_id_from_public(public_key: &[u8; 32]) -> String {
     format!("bp1_{}", hex::encode(&digest[..8]))
 }
 
+/// Signature suite a vault key (and the events it signs) can declare.
+///
+/// Named after the JOSE `alg` values they correspond to, the convention each
+/// event's `alg` field uses. `keys.json`'s `"algorithm"` field uses the same
+/// names, except that it may also say `"Ed25519"` for `EdDSA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SignatureAlgorithm {
+    EdDSA,
+    Es256,
+    Rs256,
+}
+
+impl SignatureAlgorithm {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "EdDSA" | "Ed25519" => Ok(SignatureAlgorithm::EdDSA),
+            "ES256" => Ok(SignatureAlgorithm::Es256),
+            "RS256" => Ok(SignatureAlgorithm::Rs256),
+            other => Err(format!("unknown signature algorithm: {other}")),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SignatureAlgorithm::EdDSA => "EdDSA",
+            SignatureAlgorithm::Es256 => "ES256",
+            SignatureAlgorithm::Rs256 => "RS256",
+        }
+    }
+}
+
+/// A decoded public key, tagged by the suite it was parsed for.
+enum PublicKeyMaterial {
+    EdDSA(VerifyingKey),
+    Es256(p256::ecdsa::VerifyingKey),
+    Rs256(rsa::RsaPublicKey),
+}
+
+/// Decode `public_key_b64` according to `algorithm`'s wire format: a raw
+/// 32-byte point for EdDSA, a SEC1 point for ES256, or a DER
+/// SubjectPublicKeyInfo for RS256.
+fn parse_public_key(algorithm: SignatureAlgorithm, key_id: &str, public_key_b64: &str) -> Result<PublicKeyMaterial, String> {
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(public_key_b64)
+        .map_err(|e| format!("invalid base64 pubkey for {key_id}: {e}"))?;
+
+    match algorithm {
+        SignatureAlgorithm::EdDSA => {
+            if decoded.len() != 32 {
+                return Err(format!("public key {key_id} must be 32 bytes for EdDSA"));
+            }
+            let mut pk = [0u8; 32];
+            pk.copy_from_slice(&decoded);
+            let verifying_key = VerifyingKey::from_bytes(&pk)
+                .map_err(|e| format!("invalid EdDSA key for {key_id}: {e}"))?;
+            Ok(PublicKeyMaterial::EdDSA(verifying_key))
+        }
+        SignatureAlgorithm::Es256 => {
+            let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(&decoded)
+                .map_err(|e| format!("invalid ES256 key for {key_id}: {e}"))?;
+            Ok(PublicKeyMaterial::Es256(verifying_key))
+        }
+        SignatureAlgorithm::Rs256 => {
+            let public_key = rsa::RsaPublicKey::from_public_key_der(&decoded)
+                .map_err(|e| format!("invalid RS256 key for {key_id}: {e}"))?;
+            Ok(PublicKeyMaterial::Rs256(public_key))
+        }
+    }
+}
+
+/// Verify `sig_bytes` over the pre-computed canonical-JCS SHA-256 digest
+/// `hash`, dispatching on `algorithm`. Every suite verifies the same digest
+/// directly rather than re-hashing, so switching suites never changes what
+/// is actually signed.
+fn verify_signature(
+    algorithm: SignatureAlgorithm,
+    key: &PublicKeyMaterial,
+    hash: &[u8],
+    sig_bytes: &[u8],
+) -> Result<(), String> {
+    match (algorithm, key) {
+        (SignatureAlgorithm::EdDSA, PublicKeyMaterial::EdDSA(verifying_key)) => {
+            let signature = Signature::from_slice(sig_bytes)
+                .map_err(|e| format!("invalid EdDSA signature: {e}"))?;
+            verifying_key
+                .verify(hash, &signature)
+                .map_err(|e| format!("EdDSA verification failed: {e}"))
+        }
+        (SignatureAlgorithm::Es256, PublicKeyMaterial::Es256(verifying_key)) => {
+            let signature = p256::ecdsa::Signature::from_slice(sig_bytes)
+                .map_err(|e| format!("invalid ES256 signature: {e}"))?;
+            verifying_key
+                .verify_prehash(hash, &signature)
+                .map_err(|e| format!("ES256 verification failed: {e}"))
+        }
+        (SignatureAlgorithm::Rs256, PublicKeyMaterial::Rs256(public_key)) => public_key
+            .verify(Pkcs1v15Sign::new::<Sha256>(), hash, sig_bytes)
+            .map_err(|e| format!("RS256 verification failed: {e}")),
+        (algorithm, _) => Err(format!(
+            "key material does not match declared algorithm {}",
+            algorithm.as_str()
+        )),
+    }
+}
+
+/// Lifecycle state of a vault key, from `keys.json`'s `status` (and, for an
+/// explicit cutoff, `revoked_at_event_id`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum KeyStatus {
+    /// Usable once introduced along an actor's chain.
+    Active,
+    /// Usable up to and including `revoked_at_event_id`; invalid from the
+    /// next event in that actor's chain onward.
+    RevokedAt(String),
+    /// Revoked with no chain position recorded — never valid.
+    RevokedAlways,
+}
+
+struct KeyEntry {
+    algorithm: SignatureAlgorithm,
+    material: PublicKeyMaterial,
+    status: KeyStatus,
+    /// Resource patterns (e.g. `"door:*"`) this key is a root authority
+    /// over, needing no `DELEGATION` proof to act within them.
+    owns: Vec<String>,
+}
+
+/// A capability: an action set over a `subject:predicate` resource pattern.
+struct Capability {
+    resource: String,
+    actions: std::collections::BTreeSet<String>,
+}
+
+fn parse_capability(value: &Value) -> Result<Capability, String> {
+    let resource = value
+        .get("resource")
+        .and_then(|v| v.as_str())
+        .ok_or("capability missing resource")?
+        .to_string();
+    let actions = value
+        .get("actions")
+        .and_then(|v| v.as_array())
+        .ok_or("capability missing actions")?
+        .iter()
+        .map(|a| {
+            a.as_str()
+                .map(|s| s.to_string())
+                .ok_or_else(|| "capability action must be a string".to_string())
+        })
+        .collect::<Result<std::collections::BTreeSet<String>, String>>()?;
+    Ok(Capability { resource, actions })
+}
+
+/// A `DELEGATION` event's payload: `iss` grants `aud` `capabilities`,
+/// attenuated from whatever `iss` itself holds via `proof` (the event_id of
+/// the parent delegation), or directly if `iss` is a root owner.
+struct DelegationRecord {
+    iss: String,
+    aud: String,
+    capabilities: Vec<Capability>,
+    proof: Option<String>,
+    expires_at: Option<String>,
+}
+
+fn resource_pattern_matches(pattern: &str, resource: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => resource.starts_with(prefix),
+        None => pattern == resource,
+    }
+}
+
+/// True if every resource `child` can match is also matched by `parent` —
+/// i.e. `child` is at least as narrow, so attenuating to it never widens
+/// authority.
+fn pattern_covered_by(child: &str, parent: &str) -> bool {
+    if child == parent {
+        return true;
+    }
+    match parent.strip_suffix('*') {
+        Some(parent_prefix) => match child.strip_suffix('*') {
+            Some(child_prefix) => child_prefix.starts_with(parent_prefix),
+            None => child.starts_with(parent_prefix),
+        },
+        None => false,
+    }
+}
+
+/// Walk `delegation`'s `proof` chain to confirm it (and every ancestor) is
+/// itself entitled to grant a capability at least as broad as `resource`
+/// `action`, terminating at a root owner.
+///
+/// `visited` tracks the `proof` event_ids already walked in this chain.
+/// `verify_vault` never re-derives an event_id from the event's content, so
+/// proof references are effectively free-form strings and a crafted vault
+/// can make two DELEGATION events point at each other; without this check
+/// that cycle would recurse unboundedly.
+fn delegation_authorizes(
+    delegation: &DelegationRecord,
+    resource: &str,
+    action: &str,
+    event_timestamp: Option<&str>,
+    key_map: &BTreeMap<String, KeyEntry>,
+    delegations_by_event_id: &BTreeMap<String, DelegationRecord>,
+    visited: &mut std::collections::BTreeSet<String>,
+) -> bool {
+    if let Some(expires_at) = &delegation.expires_at {
+        if let Some(ts) = event_timestamp {
+            if ts > expires_at.as_str() {
+                return false;
+            }
+        }
+    }
+
+    let Some(granted) = delegation
+        .capabilities
+        .iter()
+        .find(|c| resource_pattern_matches(&c.resource, resource) && c.actions.contains(action))
+    else {
+        return false;
+    };
+
+    match &delegation.proof {
+        None => key_map
+            .get(&delegation.iss)
+            .map(|k| k.owns.iter().any(|p| resource_pattern_matches(p, resource)))
+            .unwrap_or(false),
+        Some(parent_id) => {
+            if !visited.insert(parent_id.clone()) {
+                return false;
+            }
+            let Some(parent) = delegations_by_event_id.get(parent_id) else {
+                return false;
+            };
+            if parent.aud != delegation.iss {
+                return false;
+            }
+            let attenuated = parent
+                .capabilities
+                .iter()
+                .any(|c| pattern_covered_by(&granted.resource, &c.resource) && granted.actions.is_subset(&c.actions));
+            if !attenuated {
+                return false;
+            }
+            delegation_authorizes(parent, resource, action, event_timestamp, key_map, delegations_by_event_id, visited)
+        }
+    }
+}
+
+/// True if `key_id` is authorized for `action` on `resource`, either as a
+/// root owner or via some valid `DELEGATION` chain granting it to `key_id`.
+fn is_authorized(
+    key_id: &str,
+    resource: &str,
+    action: &str,
+    event_timestamp: Option<&str>,
+    key_map: &BTreeMap<String, KeyEntry>,
+    delegations_by_event_id: &BTreeMap<String, DelegationRecord>,
+    delegations_by_aud: &BTreeMap<String, Vec<String>>,
+) -> bool {
+    if key_map
+        .get(key_id)
+        .map(|k| k.owns.iter().any(|p| resource_pattern_matches(p, resource)))
+        .unwrap_or(false)
+    {
+        return true;
+    }
+
+    delegations_by_aud
+        .get(key_id)
+        .map(|event_ids| {
+            event_ids.iter().any(|id| {
+                let mut visited = std::collections::BTreeSet::new();
+                visited.insert(id.clone());
+                delegation_authorizes(&delegations_by_event_id[id], resource, action, event_timestamp, key_map, delegations_by_event_id, &mut visited)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// The set of keys currently valid (and previously retired) for one actor,
+/// as of the event last processed along that actor's chain.
+#[derive(Debug, Default)]
+struct ActorKeyState {
+    active: std::collections::BTreeSet<String>,
+    retired: std::collections::BTreeSet<String>,
+}
+
 fn verify_vault(vault: &Path) -> Result<(), String> {
     let events_path = vault.join("events").join("events.ndjson");
     let keys_path = vault.join("identity").join("keys.json");
@@ -28,25 +320,44 @@ fn verify_vault(vault: &Path) -> Result<(), String> {
     let keys_json: Value = serde_json::from_str(&keys_raw)
         .map_err(|e| format!("invalid keys.json: {e}"))?;
 
-    let mut key_map: BTreeMap<String, [u8; 32]> = BTreeMap::new();
+    let mut key_map: BTreeMap<String, KeyEntry> = BTreeMap::new();
     if let Some(entries) = keys_json.get("keys").and_then(|v| v.as_array()) {
         for entry in entries {
             let kid = entry.get("key_id").and_then(|v| v.as_str());
             let pub_b64 = entry.get("public_key_b64").and_then(|v| v.as_str());
+            let algorithm = entry
+                .get("algorithm")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Ed25519");
+            let status_raw = entry.get("status").and_then(|v| v.as_str()).unwrap_or("active");
+            let revoked_at = entry
+                .get("revoked_at_event_id")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+
             if let (Some(k), Some(p)) = (kid, pub_b64) {
-                let decoded = base64::engine::general_purpose::STANDARD
-                    .decode(p)
-                    .map_err(|e| format!("invalid base64 pubkey for {k}: {e}"))?;
-                if decoded.len() != 32 {
-                    return Err(format!("public key {k} must be 32 bytes"));
-                }
-                let mut pk = [0u8; 32];
-                pk.copy_from_slice(&decoded);
-                key_map.insert(k.to_string(), pk);
+                let algorithm = SignatureAlgorithm::parse(algorithm)?;
+                let material = parse_public_key(algorithm, k, p)?;
+                let status = match (status_raw, revoked_at) {
+                    ("active", _) => KeyStatus::Active,
+                    ("revoked", Some(event_id)) => KeyStatus::RevokedAt(event_id),
+                    ("revoked", None) => KeyStatus::RevokedAlways,
+                    (other, _) => return Err(format!("key {k} has unknown status {other}")),
+                };
+                let owns = entry
+                    .get("owns")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                    .unwrap_or_default();
+                key_map.insert(k.to_string(), KeyEntry { algorithm, material, status, owns });
             }
         }
     }
 
+    let mut actor_key_states: BTreeMap<String, ActorKeyState> = BTreeMap::new();
+    let mut delegation_by_event_id: BTreeMap<String, DelegationRecord> = BTreeMap::new();
+    let mut delegations_by_aud: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
     let content = fs::read_to_string(&events_path)
         .map_err(|e| format!("failed to read {}: {e}", events_path.display()))?;
 
@@ -88,45 +399,304 @@ fn verify_vault(vault: &Path) -> Result<(), String> {
             }
         }
 
-        if let (Some(sig_b64), Some(kid)) = (
-            event.get("sig").and_then(|v| v.as_str()),
-            event.get("actor_key_id").and_then(|v| v.as_str()),
-        ) {
-            let pubkey = key_map
-                .get(kid)
-                .ok_or_else(|| format!("event {event_id} references unknown key_id {kid}"))?;
-            let verifying_key = VerifyingKey::from_bytes(pubkey)
-                .map_err(|e| format!("invalid key for {kid}: {e}"))?;
+        let event_type = event.get("type").and_then(|v| v.as_str()).unwrap_or("");
 
-            let mut signing_obj = event.clone();
-            if let Some(map) = signing_obj.as_object_mut() {
-                map.remove("sig");
+        // Every event in this vault format must be signed by its actor's key;
+        // an ASSERTION/ATTESTATION that simply omits `sig`/`actor_key_id`
+        // must not be able to skip signature and authorization checks by
+        // falling through an `if let`.
+        let sig_b64 = event
+            .get("sig")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("event {event_id} missing required sig"))?;
+        let kid = event
+            .get("actor_key_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("event {event_id} missing required actor_key_id"))?;
+
+        let key_entry = key_map
+            .get(kid)
+            .ok_or_else(|| format!("event {event_id} references unknown key_id {kid}"))?;
+
+        if key_entry.status == KeyStatus::RevokedAlways {
+            return Err(format!("event {event_id} uses key {kid}, which is revoked"));
+        }
+
+        let state = actor_key_states.entry(actor.clone()).or_default();
+        if state.retired.contains(kid) {
+            return Err(format!(
+                "event {event_id} uses key {kid}, which was already revoked or rotated out for actor {actor}"
+            ));
+        }
+        if !state.active.contains(kid) {
+            if state.active.is_empty() && state.retired.is_empty() {
+                // First use of any key by this actor: introduce it.
+                state.active.insert(kid.to_string());
+            } else {
+                return Err(format!(
+                    "event {event_id} uses key {kid}, which was not yet introduced for actor {actor}"
+                ));
             }
-            let canonical = canonical_bytes(&signing_obj)?;
-            let hash = Sha256::digest(&canonical);
+        }
 
-            let sig_bytes = base64::engine::general_purpose::STANDARD
-                .decode(sig_b64)
-                .map_err(|e| format!("invalid signature b64 on event {event_id}: {e}"))?;
-            let signature = Signature::from_slice(&sig_bytes)
-                .map_err(|e| format!("invalid signature on event {event_id}: {e}"))?;
+        let event_alg = match event.get("alg").and_then(|v| v.as_str()) {
+            Some(s) => SignatureAlgorithm::parse(s)?,
+            None => SignatureAlgorithm::EdDSA,
+        };
+        if event_alg != key_entry.algorithm {
+            return Err(format!(
+                "event {event_id} declares alg {}, but key {kid} declares {}",
+                event_alg.as_str(),
+                key_entry.algorithm.as_str()
+            ));
+        }
 
-            verifying_key
-                .verify(&hash, &signature)
-                .map_err(|e| format!("signature verification failed on {event_id}: {e}"))?;
+        let mut signing_obj = event.clone();
+        if let Some(map) = signing_obj.as_object_mut() {
+            map.remove("sig");
+        }
+        let canonical = canonical_bytes(&signing_obj)?;
+        let hash = Sha256::digest(&canonical);
+
+        let sig_bytes = base64::engine::general_purpose::STANDARD
+            .decode(sig_b64)
+            .map_err(|e| format!("invalid signature b64 on event {event_id}: {e}"))?;
+
+        verify_signature(event_alg, &key_entry.material, &hash, &sig_bytes)
+            .map_err(|e| format!("signature verification failed on {event_id}: {e}"))?;
+
+        if event_type == "KEY_ROTATION" {
+            let payload = event
+                .get("payload")
+                .ok_or_else(|| format!("rotation event {event_id} missing payload"))?;
+            let outgoing = payload
+                .get("outgoing_key_id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("rotation event {event_id} missing payload.outgoing_key_id"))?;
+            let incoming = payload
+                .get("incoming_key_id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("rotation event {event_id} missing payload.incoming_key_id"))?;
+            if outgoing != kid {
+                return Err(format!(
+                    "rotation event {event_id} is signed by {kid} but names outgoing key {outgoing}"
+                ));
+            }
+
+            let incoming_entry = key_map.get(incoming).ok_or_else(|| {
+                format!("rotation event {event_id} references unknown incoming_key_id {incoming}")
+            })?;
+            if incoming_entry.status == KeyStatus::RevokedAlways {
+                return Err(format!(
+                    "rotation event {event_id} names incoming key {incoming}, which is revoked"
+                ));
+            }
+
+            let incoming_sig_b64 = event
+                .get("incoming_sig")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("rotation event {event_id} missing incoming_sig"))?;
+            let incoming_sig_bytes = base64::engine::general_purpose::STANDARD
+                .decode(incoming_sig_b64)
+                .map_err(|e| format!("invalid incoming_sig b64 on rotation {event_id}: {e}"))?;
+            let payload_hash = Sha256::digest(&canonical_bytes(payload)?);
+
+            verify_signature(incoming_entry.algorithm, &incoming_entry.material, &payload_hash, &incoming_sig_bytes)
+                .map_err(|e| format!("incoming-key signature verification failed on rotation {event_id}: {e}"))?;
+
+            state.active.remove(outgoing);
+            state.retired.insert(outgoing.to_string());
+            state.active.insert(incoming.to_string());
+        }
+
+        if let KeyStatus::RevokedAt(boundary_event_id) = &key_entry.status {
+            if boundary_event_id == &event_id {
+                state.active.remove(kid);
+                state.retired.insert(kid.to_string());
+            }
+        }
+
+        if event_type == "ASSERTION" || event_type == "ATTESTATION" {
+            let payload = event
+                .get("payload")
+                .ok_or_else(|| format!("{event_type} event {event_id} missing payload"))?;
+            let subject = payload
+                .get("subject")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("{event_type} event {event_id} missing payload.subject"))?;
+            let predicate = payload
+                .get("predicate")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("{event_type} event {event_id} missing payload.predicate"))?;
+            let resource = format!("{subject}:{predicate}");
+            let action = if event_type == "ASSERTION" { "ASSERT" } else { "ATTEST" };
+            let timestamp = event.get("timestamp_utc").and_then(|v| v.as_str());
+
+            if !is_authorized(kid, &resource, action, timestamp, &key_map, &delegation_by_event_id, &delegations_by_aud) {
+                return Err(format!(
+                    "{event_type} event {event_id}: key {kid} lacks a valid capability for {action} on {resource}"
+                ));
+            }
+        }
+
+        if event_type == "DELEGATION" {
+            let payload = event
+                .get("payload")
+                .ok_or_else(|| format!("DELEGATION event {event_id} missing payload"))?;
+            let iss = payload
+                .get("iss")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("DELEGATION event {event_id} missing payload.iss"))?
+                .to_string();
+            let aud = payload
+                .get("aud")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("DELEGATION event {event_id} missing payload.aud"))?
+                .to_string();
+            if iss != kid {
+                return Err(format!(
+                    "DELEGATION event {event_id} is signed by {kid} but names issuer {iss}"
+                ));
+            }
+            let capabilities = payload
+                .get("capabilities")
+                .and_then(|v| v.as_array())
+                .ok_or_else(|| format!("DELEGATION event {event_id} missing payload.capabilities"))?
+                .iter()
+                .map(parse_capability)
+                .collect::<Result<Vec<Capability>, String>>()
+                .map_err(|e| format!("DELEGATION event {event_id}: {e}"))?;
+            let proof = payload.get("proof").and_then(|v| v.as_str()).map(|s| s.to_string());
+            let expires_at = payload.get("expires_at").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+            delegations_by_aud.entry(aud.clone()).or_default().push(event_id.clone());
+            delegation_by_event_id.insert(
+                event_id.clone(),
+                DelegationRecord { iss, aud, capabilities, proof, expires_at },
+            );
         }
 
         last_by_actor.insert(actor, event_id);
     }
 
+    for (actor, state) in &actor_key_states {
+        match state.active.iter().next() {
+            Some(active_key) => println!("{actor}: active_key={active_key}"),
+            None => println!("{actor}: no active key (all keys retired or revoked)"),
+        }
+    }
+
     Ok(())
 }
 
-fn create_vault(vault: &Path) -> Result<(), String> {
+/// The default BIP-32-style path SLIP-0010 derivation uses when
+/// `--derivation-path` is not given.
+const DEFAULT_DERIVATION_PATH: &str = "m/44'/0'/0'";
+
+/// An extended Ed25519 key per SLIP-0010: a 32-byte private key and a
+/// 32-byte chain code used to derive children.
+struct ExtendedKey {
+    key: [u8; 32],
+    chain_code: [u8; 32],
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts a key of any length");
+    mac.update(data);
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+/// SLIP-0010 master key: `I = HMAC-SHA512(key = "ed25519 seed", data = seed)`.
+fn slip10_master_key(seed: &[u8]) -> ExtendedKey {
+    let i = hmac_sha512(b"ed25519 seed", seed);
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&i[..32]);
+    chain_code.copy_from_slice(&i[32..]);
+    ExtendedKey { key, chain_code }
+}
+
+/// SLIP-0010 hardened child derivation. Ed25519 has no unhardened
+/// derivation, so `index` must already be `>= 2^31`.
+fn slip10_derive_child(parent: &ExtendedKey, index: u32) -> Result<ExtendedKey, String> {
+    if index < 0x8000_0000 {
+        return Err("Ed25519 SLIP-0010 derivation only supports hardened indices (>= 2^31)".to_string());
+    }
+    let mut data = Vec::with_capacity(1 + 32 + 4);
+    data.push(0u8);
+    data.extend_from_slice(&parent.key);
+    data.extend_from_slice(&index.to_be_bytes());
+
+    let i = hmac_sha512(&parent.chain_code, &data);
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&i[..32]);
+    chain_code.copy_from_slice(&i[32..]);
+    Ok(ExtendedKey { key, chain_code })
+}
+
+/// Parse a path like `m/44'/0'/0'` into hardened child indices
+/// (`component + 2^31`). Every component must be hardened (`'` or `h`
+/// suffix), since Ed25519 supports no other kind.
+fn parse_derivation_path(path: &str) -> Result<Vec<u32>, String> {
+    let mut parts = path.split('/');
+    let first = parts.next().ok_or_else(|| "empty derivation path".to_string())?;
+    if first != "m" {
+        return Err(format!("derivation path must start with 'm', got {first}"));
+    }
+    parts
+        .map(|segment| {
+            let hardened = segment.ends_with('\'') || segment.ends_with('h');
+            if !hardened {
+                return Err(format!("Ed25519 derivation requires a hardened path segment, got {segment}"));
+            }
+            let digits = segment.trim_end_matches(['\'', 'h']);
+            let index: u32 = digits
+                .parse()
+                .map_err(|_| format!("invalid derivation path segment: {segment}"))?;
+            index
+                .checked_add(0x8000_0000)
+                .ok_or_else(|| format!("derivation path segment out of range: {segment}"))
+        })
+        .collect()
+}
+
+/// Derive the Ed25519 signing seed at `path` from a SLIP-0010 master seed.
+fn derive_ed25519_key(seed: &[u8], path: &str) -> Result<[u8; 32], String> {
+    let indices = parse_derivation_path(path)?;
+    let mut node = slip10_master_key(seed);
+    for index in indices {
+        node = slip10_derive_child(&node, index)?;
+    }
+    Ok(node.key)
+}
+
+/// Resolve a master seed from `--mnemonic` (a BIP-39 phrase, no passphrase)
+/// or `--seed-hex` (raw hex-encoded seed bytes); exactly one is required.
+fn resolve_seed(args: &[String]) -> Result<Vec<u8>, String> {
+    let mnemonic = arg_value(args, "--mnemonic");
+    let seed_hex = arg_value(args, "--seed-hex");
+    match (mnemonic, seed_hex) {
+        (Some(words), None) => {
+            let mnemonic = Mnemonic::parse(&words).map_err(|e| format!("invalid mnemonic: {e}"))?;
+            Ok(mnemonic.to_seed("").to_vec())
+        }
+        (None, Some(hex_str)) => hex::decode(&hex_str).map_err(|e| format!("invalid --seed-hex: {e}")),
+        (None, None) => Err("one of --mnemonic or --seed-hex is required".to_string()),
+        (Some(_), Some(_)) => Err("specify only one of --mnemonic or --seed-hex".to_string()),
+    }
+}
+
+fn create_vault(vault: &Path, args: &[String]) -> Result<(), String> {
     fs::create_dir_all(vault.join("events")).map_err(|e| e.to_string())?;
     fs::create_dir_all(vault.join("identity")).map_err(|e| e.to_string())?;
 
-    let seed = [7u8; 32];
+    let master_seed = resolve_seed(args)?;
+    let derivation_path = arg_value(args, "--derivation-path").unwrap_or_else(|| DEFAULT_DERIVATION_PATH.to_string());
+    let seed = derive_ed25519_key(&master_seed, &derivation_path)?;
+
     let signing_key = SigningKey::from_bytes(&seed);
     let verifying_key = signing_key.verifying_key();
     let public = verifying_key.to_bytes();
@@ -137,7 +707,8 @@ fn create_vault(vault: &Path) -> Result<(), String> {
             "key_id": key_id,
             "algorithm": "Ed25519",
             "public_key_b64": base64::engine::general_purpose::STANDARD.encode(public),
-            "status": "active"
+            "status": "active",
+            "derivation_path": derivation_path
         }]
     });
     fs::write(vault.join("identity").join("keys.json"), serde_json::to_string_pretty(&keys_json).unwrap())
@@ -158,6 +729,7 @@ fn create_vault(vault: &Path) -> Result<(), String> {
     let event_id_hash = sha256_hex(&canonical_bytes(&event)?);
     event["event_id"] = Value::String(format!("evt_{}", &event_id_hash[..24]));
     event["actor_key_id"] = Value::String(key_id);
+    event["alg"] = Value::String(SignatureAlgorithm::EdDSA.as_str().to_string());
 
     let signature_b64 = sign_event_json_internal(
         base64::engine::general_purpose::STANDARD.encode(seed),
@@ -171,6 +743,20 @@ fn create_vault(vault: &Path) -> Result<(), String> {
     Ok(())
 }
 
+fn derive_key_cmd(args: &[String]) -> Result<(), String> {
+    let master_seed = resolve_seed(args)?;
+    let path = arg_value(args, "--path").ok_or_else(|| "--path is required".to_string())?;
+    let seed = derive_ed25519_key(&master_seed, &path)?;
+
+    let signing_key = SigningKey::from_bytes(&seed);
+    let public = signing_key.verifying_key().to_bytes();
+    let key_id = key_id_from_public(&public);
+
+    println!("key_id={key_id}");
+    println!("public_key_b64={}", base64::engine::general_purpose::STANDARD.encode(public));
+    Ok(())
+}
+
 fn sign_event_json_internal(private_key_b64: String, event_json: String) -> Result<String, String> {
     let priv_bytes = base64::engine::general_purpose::STANDARD
         .decode(private_key_b64)
@@ -194,7 +780,8 @@ fn usage() {
     eprintln!("Usage:");
     eprintln!("  cross_impl canonical-sha256 --input-json <json>");
     eprintln!("  cross_impl verify-vault --vault <path>");
-    eprintln!("  cross_impl create-vault --vault <path>");
+    eprintln!("  cross_impl create-vault --vault <path> (--mnemonic <phrase> | --seed-hex <hex>) [--derivation-path <path>]");
+    eprintln!("  cross_impl derive-key --path <path> (--mnemonic <phrase> | --seed-hex <hex>)");
     eprintln!("  cross_impl sign-event-json --private-key-b64 <b64> --event-json <json>");
 }
 
@@ -227,8 +814,9 @@ fn main() {
         }
         "create-vault" => {
             let vault = arg_value(&args, "--vault").ok_or_else(|| "--vault is required".to_string());
-            vault.and_then(|p| create_vault(Path::new(&p)))
+            vault.and_then(|p| create_vault(Path::new(&p), &args))
         }
+        "derive-key" => derive_key_cmd(&args),
         "sign-event-json" => {
             let private_key = arg_value(&args, "--private-key-b64")
                 .ok_or_else(|| "--private-key-b64 is required".to_string());
@@ -248,3 +836,218 @@ fn main() {
         std::process::exit(1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static VAULT_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    /// A fresh, empty vault directory under the OS temp dir, unique per call.
+    fn temp_vault() -> std::path::PathBuf {
+        let n = VAULT_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let dir = std::env::temp_dir().join(format!("cross_impl_test_{}_{n}", std::process::id()));
+        fs::create_dir_all(dir.join("events")).unwrap();
+        fs::create_dir_all(dir.join("identity")).unwrap();
+        dir
+    }
+
+    fn write_keys(vault: &Path, keys: Value) {
+        fs::write(vault.join("identity").join("keys.json"), serde_json::to_string_pretty(&keys).unwrap()).unwrap();
+    }
+
+    fn write_events(vault: &Path, events: &[Value]) {
+        let body: String = events
+            .iter()
+            .map(|e| String::from_utf8(canonical_bytes(e).unwrap()).unwrap() + "\n")
+            .collect();
+        fs::write(vault.join("events").join("events.ndjson"), body).unwrap();
+    }
+
+    fn new_signing_key() -> SigningKey {
+        SigningKey::generate(&mut rand::thread_rng())
+    }
+
+    fn b64(bytes: impl AsRef<[u8]>) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    fn key_entry_json(key_id: &str, signing_key: &SigningKey, status: &str, owns: &[&str]) -> Value {
+        json!({
+            "key_id": key_id,
+            "algorithm": "Ed25519",
+            "public_key_b64": b64(signing_key.verifying_key().to_bytes()),
+            "status": status,
+            "owns": owns,
+        })
+    }
+
+    /// Sign `event` (which must not yet carry a `sig` field) and attach it.
+    fn sign_event(signing_key: &SigningKey, mut event: Value) -> Value {
+        let hash = Sha256::digest(&canonical_bytes(&event).unwrap());
+        event["sig"] = Value::String(b64(signing_key.sign(&hash).to_bytes()));
+        event
+    }
+
+    fn event(event_id: &str, actor: &str, prev: Option<&str>, kid: &str, event_type: &str, payload: Value) -> Value {
+        json!({
+            "event_id": event_id,
+            "type": event_type,
+            "actor": actor,
+            "actor_key_id": kid,
+            "prev_event_hash": prev,
+            "alg": "EdDSA",
+            "timestamp_utc": "2026-01-01T00:00:00+00:00",
+            "payload": payload,
+        })
+    }
+
+    #[test]
+    fn test_alg_mismatch_is_rejected() {
+        let vault = temp_vault();
+        let signer = new_signing_key();
+        write_keys(&vault, json!({"keys": [key_entry_json("k1", &signer, "active", &[])]}));
+
+        let mut evt = event("evt1", "actor1", None, "k1", "OBSERVATION", json!({"subject": "s", "predicate": "p", "value": 1}));
+        evt["alg"] = Value::String("RS256".to_string());
+        let evt = sign_event(&signer, evt);
+        write_events(&vault, &[evt]);
+
+        let err = verify_vault(&vault).unwrap_err();
+        assert!(err.contains("declares alg"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_rotation_requires_incoming_signature() {
+        let vault = temp_vault();
+        let outgoing = new_signing_key();
+        let incoming = new_signing_key();
+        write_keys(
+            &vault,
+            json!({"keys": [
+                key_entry_json("out1", &outgoing, "active", &["door:*"]),
+                key_entry_json("in1", &incoming, "active", &[]),
+            ]}),
+        );
+
+        let e1 = sign_event(&outgoing, event("evt1", "actor1", None, "out1", "OBSERVATION", json!({"subject": "s", "predicate": "p", "value": 1})));
+        let e2 = sign_event(
+            &outgoing,
+            event("evt2", "actor1", Some("evt1"), "out1", "KEY_ROTATION", json!({"outgoing_key_id": "out1", "incoming_key_id": "in1"})),
+        );
+        write_events(&vault, &[e1, e2]);
+
+        let err = verify_vault(&vault).unwrap_err();
+        assert!(err.contains("missing incoming_sig"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_retired_key_rejected_after_rotation() {
+        let vault = temp_vault();
+        let outgoing = new_signing_key();
+        let incoming = new_signing_key();
+        write_keys(
+            &vault,
+            json!({"keys": [
+                key_entry_json("out1", &outgoing, "active", &["door:*"]),
+                key_entry_json("in1", &incoming, "active", &[]),
+            ]}),
+        );
+
+        let e1 = sign_event(&outgoing, event("evt1", "actor1", None, "out1", "OBSERVATION", json!({"subject": "s", "predicate": "p", "value": 1})));
+        let mut rotation = event("evt2", "actor1", Some("evt1"), "out1", "KEY_ROTATION", json!({"outgoing_key_id": "out1", "incoming_key_id": "in1"}));
+        let payload_hash = Sha256::digest(&canonical_bytes(&rotation["payload"]).unwrap());
+        rotation["incoming_sig"] = Value::String(b64(incoming.sign(&payload_hash).to_bytes()));
+        let e2 = sign_event(&outgoing, rotation);
+        let e3 = sign_event(&outgoing, event("evt3", "actor1", Some("evt2"), "out1", "OBSERVATION", json!({"subject": "s", "predicate": "p", "value": 2})));
+        write_events(&vault, &[e1, e2, e3]);
+
+        let err = verify_vault(&vault).unwrap_err();
+        assert!(err.contains("already revoked or rotated out"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_key_not_yet_introduced_rejected() {
+        let vault = temp_vault();
+        let first = new_signing_key();
+        let other = new_signing_key();
+        write_keys(
+            &vault,
+            json!({"keys": [
+                key_entry_json("k1", &first, "active", &["door:*"]),
+                key_entry_json("k2", &other, "active", &[]),
+            ]}),
+        );
+
+        let e1 = sign_event(&first, event("evt1", "actor1", None, "k1", "OBSERVATION", json!({"subject": "s", "predicate": "p", "value": 1})));
+        let e2 = sign_event(&other, event("evt2", "actor1", Some("evt1"), "k2", "OBSERVATION", json!({"subject": "s", "predicate": "p", "value": 2})));
+        write_events(&vault, &[e1, e2]);
+
+        let err = verify_vault(&vault).unwrap_err();
+        assert!(err.contains("not yet introduced"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn test_assertion_requires_authorization_then_delegation_permits_it() {
+        let owner = new_signing_key();
+        let delegate = new_signing_key();
+
+        // Without a delegation, the delegate's key has no capability.
+        let vault = temp_vault();
+        write_keys(
+            &vault,
+            json!({"keys": [
+                key_entry_json("owner1", &owner, "active", &["door:*"]),
+                key_entry_json("delegate1", &delegate, "active", &[]),
+            ]}),
+        );
+        let unauthorized = sign_event(&delegate, event("evt1", "actor1", None, "delegate1", "ASSERTION", json!({"subject": "door", "predicate": "status", "value": "locked"})));
+        write_events(&vault, &[unauthorized]);
+        let err = verify_vault(&vault).unwrap_err();
+        assert!(err.contains("lacks a valid capability"), "unexpected error: {err}");
+
+        // With a DELEGATION from the owner granting ASSERT on door:status,
+        // the same assertion is authorized.
+        let vault = temp_vault();
+        write_keys(
+            &vault,
+            json!({"keys": [
+                key_entry_json("owner1", &owner, "active", &["door:*"]),
+                key_entry_json("delegate1", &delegate, "active", &[]),
+            ]}),
+        );
+        let delegation = sign_event(
+            &owner,
+            event(
+                "evt1",
+                "owner_actor",
+                None,
+                "owner1",
+                "DELEGATION",
+                json!({
+                    "iss": "owner1",
+                    "aud": "delegate1",
+                    "capabilities": [{"resource": "door:status", "actions": ["ASSERT"]}],
+                }),
+            ),
+        );
+        let assertion = sign_event(&delegate, event("evt2", "actor1", None, "delegate1", "ASSERTION", json!({"subject": "door", "predicate": "status", "value": "locked"})));
+        write_events(&vault, &[delegation, assertion]);
+        assert!(verify_vault(&vault).is_ok());
+    }
+
+    #[test]
+    fn test_slip10_ed25519_known_answer_vector() {
+        // SLIP-0010 Ed25519 test vector 1: seed 000102030405060708090a0b0c0d0e0f.
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        let master = slip10_master_key(&seed);
+        assert_eq!(hex::encode(master.key), "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7");
+        assert_eq!(hex::encode(master.chain_code), "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb");
+
+        let child = slip10_derive_child(&master, 0x8000_0000).unwrap();
+        assert_eq!(hex::encode(child.key), "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3");
+        assert_eq!(hex::encode(child.chain_code), "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69");
+    }
+}
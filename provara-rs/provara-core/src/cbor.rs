@@ -0,0 +1,280 @@
+//! Deterministic CBOR (dCBOR) wire format — a compact transport alternative
+//! to JCS.
+//!
+//! Events are still *signed* over RFC 8785 canonical JSON; this module only
+//! offers an alternate serialization for storage/transport. The profile
+//! follows RFC 8949's core deterministic encoding: definite-length maps and
+//! arrays, shortest-form integer arguments, map keys sorted by the bytewise
+//! lexicographic order of their own encoded bytes, and no floating point —
+//! a JSON number that isn't an integer is rejected rather than silently
+//! rounded.
+
+use crate::{Event, ProvaraError};
+use serde_json::{Map, Number, Value};
+
+/// Encode a Provara event as deterministic CBOR.
+///
+/// The signing hash remains defined over JCS bytes; this is purely an
+/// alternate wire representation of the same signed event.
+pub fn event_to_dcbor(event: &Event) -> Result<Vec<u8>, ProvaraError> {
+    let value = serde_json::to_value(event).map_err(|e| ProvaraError::Serialization(e.to_string()))?;
+    encode_value(&value)
+}
+
+/// Decode a deterministic-CBOR-encoded event back into an `Event`.
+pub fn event_from_dcbor(bytes: &[u8]) -> Result<Event, ProvaraError> {
+    let mut pos = 0;
+    let value = decode_value(bytes, &mut pos)?;
+    if pos != bytes.len() {
+        return Err(ProvaraError::Serialization(format!(
+            "dCBOR input has {} trailing byte(s) after the top-level value",
+            bytes.len() - pos
+        )));
+    }
+    serde_json::from_value(value).map_err(|e| ProvaraError::Serialization(e.to_string()))
+}
+
+// ---------------------------------------------------------------------------
+// Encoding
+// ---------------------------------------------------------------------------
+
+fn encode_uint(major: u8, n: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    if n <= 23 {
+        out.push((major << 5) | n as u8);
+    } else if n <= 0xFF {
+        out.push((major << 5) | 24);
+        out.push(n as u8);
+    } else if n <= 0xFFFF {
+        out.push((major << 5) | 25);
+        out.extend_from_slice(&(n as u16).to_be_bytes());
+    } else if n <= 0xFFFF_FFFF {
+        out.push((major << 5) | 26);
+        out.extend_from_slice(&(n as u32).to_be_bytes());
+    } else {
+        out.push((major << 5) | 27);
+        out.extend_from_slice(&n.to_be_bytes());
+    }
+    out
+}
+
+fn encode_number(n: &Number) -> Result<Vec<u8>, ProvaraError> {
+    if let Some(u) = n.as_u64() {
+        Ok(encode_uint(0, u))
+    } else if let Some(i) = n.as_i64() {
+        if i >= 0 {
+            Ok(encode_uint(0, i as u64))
+        } else {
+            Ok(encode_uint(1, (-1 - i) as u64))
+        }
+    } else {
+        Err(ProvaraError::Serialization(
+            "dCBOR rejects non-integer (floating point) numbers".to_string(),
+        ))
+    }
+}
+
+fn encode_text(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut out = encode_uint(3, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+    out
+}
+
+fn encode_array(arr: &[Value]) -> Result<Vec<u8>, ProvaraError> {
+    let mut out = encode_uint(4, arr.len() as u64);
+    for item in arr {
+        out.extend(encode_value(item)?);
+    }
+    Ok(out)
+}
+
+fn encode_object(obj: &Map<String, Value>) -> Result<Vec<u8>, ProvaraError> {
+    let mut entries: Vec<(Vec<u8>, Vec<u8>)> = Vec::with_capacity(obj.len());
+    for (k, v) in obj {
+        entries.push((encode_text(k), encode_value(v)?));
+    }
+    // RFC 8949 core deterministic encoding: sort by the bytewise
+    // lexicographic order of each key's own encoded bytes.
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut out = encode_uint(5, entries.len() as u64);
+    for (key_bytes, value_bytes) in entries {
+        out.extend(key_bytes);
+        out.extend(value_bytes);
+    }
+    Ok(out)
+}
+
+fn encode_value(value: &Value) -> Result<Vec<u8>, ProvaraError> {
+    match value {
+        Value::Null => Ok(vec![0xf6]),
+        Value::Bool(false) => Ok(vec![0xf4]),
+        Value::Bool(true) => Ok(vec![0xf5]),
+        Value::Number(n) => encode_number(n),
+        Value::String(s) => Ok(encode_text(s)),
+        Value::Array(arr) => encode_array(arr),
+        Value::Object(obj) => encode_object(obj),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Decoding
+// ---------------------------------------------------------------------------
+
+fn read_argument(bytes: &[u8], pos: &mut usize, info: u8) -> Result<u64, ProvaraError> {
+    let need = |pos: &usize, n: usize| -> Result<(), ProvaraError> {
+        if *pos + n > bytes.len() {
+            Err(ProvaraError::Serialization("dCBOR input truncated".to_string()))
+        } else {
+            Ok(())
+        }
+    };
+
+    match info {
+        0..=23 => Ok(info as u64),
+        24 => {
+            need(pos, 1)?;
+            let v = bytes[*pos] as u64;
+            *pos += 1;
+            Ok(v)
+        }
+        25 => {
+            need(pos, 2)?;
+            let v = u16::from_be_bytes([bytes[*pos], bytes[*pos + 1]]) as u64;
+            *pos += 2;
+            Ok(v)
+        }
+        26 => {
+            need(pos, 4)?;
+            let v = u32::from_be_bytes(bytes[*pos..*pos + 4].try_into().unwrap()) as u64;
+            *pos += 4;
+            Ok(v)
+        }
+        27 => {
+            need(pos, 8)?;
+            let v = u64::from_be_bytes(bytes[*pos..*pos + 8].try_into().unwrap());
+            *pos += 8;
+            Ok(v)
+        }
+        _ => Err(ProvaraError::Serialization(format!(
+            "dCBOR indefinite-length/reserved items are not supported (info={})",
+            info
+        ))),
+    }
+}
+
+fn decode_value(bytes: &[u8], pos: &mut usize) -> Result<Value, ProvaraError> {
+    if *pos >= bytes.len() {
+        return Err(ProvaraError::Serialization("dCBOR input truncated".to_string()));
+    }
+    let initial = bytes[*pos];
+    *pos += 1;
+    let major = initial >> 5;
+    let info = initial & 0x1f;
+
+    match major {
+        0 => {
+            let n = read_argument(bytes, pos, info)?;
+            Ok(Value::Number(n.into()))
+        }
+        1 => {
+            let n = read_argument(bytes, pos, info)?;
+            let signed = -1i64 - n as i64;
+            Ok(Value::Number(signed.into()))
+        }
+        3 => {
+            let len = read_argument(bytes, pos, info)? as usize;
+            if *pos + len > bytes.len() {
+                return Err(ProvaraError::Serialization("dCBOR text string truncated".to_string()));
+            }
+            let s = std::str::from_utf8(&bytes[*pos..*pos + len])
+                .map_err(|e| ProvaraError::Encoding(format!("dCBOR text string is not valid UTF-8: {}", e)))?
+                .to_string();
+            *pos += len;
+            Ok(Value::String(s))
+        }
+        4 => {
+            let len = read_argument(bytes, pos, info)? as usize;
+            let mut arr = Vec::with_capacity(len);
+            for _ in 0..len {
+                arr.push(decode_value(bytes, pos)?);
+            }
+            Ok(Value::Array(arr))
+        }
+        5 => {
+            let len = read_argument(bytes, pos, info)? as usize;
+            let mut map = Map::new();
+            for _ in 0..len {
+                let key = decode_value(bytes, pos)?;
+                let key = key
+                    .as_str()
+                    .ok_or_else(|| ProvaraError::Serialization("dCBOR map key must be a text string".to_string()))?
+                    .to_string();
+                let value = decode_value(bytes, pos)?;
+                map.insert(key, value);
+            }
+            Ok(Value::Object(map))
+        }
+        7 => match info {
+            20 => Ok(Value::Bool(false)),
+            21 => Ok(Value::Bool(true)),
+            22 => Ok(Value::Null),
+            other => Err(ProvaraError::Serialization(format!(
+                "dCBOR simple value {} is not supported",
+                other
+            ))),
+        },
+        other => Err(ProvaraError::Serialization(format!(
+            "dCBOR major type {} is not supported",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_event, verify_event_signature, KeyPair};
+    use rand::thread_rng;
+    use serde_json::json;
+
+    #[test]
+    fn test_dcbor_round_trip_preserves_event_and_signature() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let event = create_event(
+            "OBSERVATION",
+            &keypair,
+            None,
+            json!({"subject": "door", "predicate": "status", "value": "open", "count": 3}),
+        ).unwrap();
+
+        let encoded = event_to_dcbor(&event).unwrap();
+        let decoded = event_from_dcbor(&encoded).unwrap();
+
+        assert_eq!(decoded.event_id, event.event_id);
+        assert_eq!(decoded.signature, event.signature);
+        assert!(verify_event_signature(&decoded, &keypair.public_key()).unwrap());
+    }
+
+    #[test]
+    fn test_dcbor_rejects_floats() {
+        let value = json!({"x": 1.5});
+        assert!(encode_value(&value).is_err());
+    }
+
+    #[test]
+    fn test_dcbor_map_keys_sorted_by_encoded_bytes() {
+        let value = json!({"b": 1, "a": 2, "aa": 3});
+        let encoded = encode_value(&value).unwrap();
+        let decoded = decode_value(&encoded, &mut 0).unwrap();
+        assert_eq!(decoded, value);
+
+        // Re-encoding must reproduce identical bytes regardless of the
+        // source map's insertion order.
+        let reordered = json!({"aa": 3, "a": 2, "b": 1});
+        assert_eq!(encode_value(&reordered).unwrap(), encoded);
+    }
+}
@@ -0,0 +1,459 @@
+//! UCAN-style capability delegation.
+//!
+//! A [`Delegation`] lets an issuer key grant a subset of its capabilities
+//! — actions (`OBSERVE`/`ASSERT`/`ATTEST`/`RETRACT`) over a `subject:predicate`
+//! resource pattern — to an audience key, optionally expiring, and optionally
+//! rooted in a chain of parent delegations ("proofs"). It is itself a
+//! canonicalized, Ed25519-signed JSON object, reusing the same
+//! `canonicalize`/`sha256_hash` primitives events do. Verification walks the
+//! proof chain checking that capabilities only narrow (attenuate) at each
+//! link and that every link's signature is valid, giving Provara a
+//! principal-to-principal authorization layer distinct from raw key
+//! ownership.
+
+use crate::{canonicalize, derive_key_id, sha256_hash, Event, KeyPair, PublicKeyBytes, ProvaraError};
+use ed25519_dalek::{Signature, Signer, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An action a capability may grant over a resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Action {
+    #[serde(rename = "OBSERVE")]
+    Observe,
+    #[serde(rename = "ASSERT")]
+    Assert,
+    #[serde(rename = "ATTEST")]
+    Attest,
+    #[serde(rename = "RETRACT")]
+    Retract,
+}
+
+/// A grant of `actions` over resources matching `resource` (a `subject:predicate`
+/// string, or a `subject:*` / `*` wildcard pattern).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capability {
+    pub resource: String,
+    pub actions: BTreeSet<Action>,
+}
+
+impl Capability {
+    fn resource_matches(pattern: &str, resource: &str) -> bool {
+        match pattern.strip_suffix('*') {
+            Some(prefix) => resource.starts_with(prefix),
+            None => pattern == resource,
+        }
+    }
+
+    /// True if this capability grants nothing beyond what `parent` grants.
+    fn attenuates(&self, parent: &Capability) -> bool {
+        Self::resource_matches(&parent.resource, &self.resource) && self.actions.is_subset(&parent.actions)
+    }
+
+    /// True if this capability covers `action` on `resource`.
+    pub fn permits(&self, resource: &str, action: Action) -> bool {
+        Self::resource_matches(&self.resource, resource) && self.actions.contains(&action)
+    }
+}
+
+/// A signed capability grant from `iss` to `aud`.
+///
+/// `aud_public_key_b64` carries the audience's Ed25519 public key so that a
+/// delegation chain can be verified purely by walking `proof` links plus one
+/// externally-trusted root key — no separate key directory is needed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delegation {
+    pub delegation_id: String,
+    pub iss: String,
+    pub aud: String,
+    pub aud_public_key_b64: String,
+    pub capabilities: Vec<Capability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl Delegation {
+    fn signing_payload(&self) -> Result<Value, ProvaraError> {
+        let mut map = Map::new();
+        map.insert("delegation_id".to_string(), json!(self.delegation_id));
+        map.insert("iss".to_string(), json!(self.iss));
+        map.insert("aud".to_string(), json!(self.aud));
+        map.insert("aud_public_key_b64".to_string(), json!(self.aud_public_key_b64));
+        map.insert(
+            "capabilities".to_string(),
+            serde_json::to_value(&self.capabilities).map_err(|e| ProvaraError::Serialization(e.to_string()))?,
+        );
+        if let Some(ref exp) = self.expires_at {
+            map.insert("expires_at".to_string(), json!(exp));
+        }
+        if let Some(ref proof) = self.proof {
+            map.insert("proof".to_string(), json!(proof));
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+fn derive_delegation_id(delegation: &Delegation) -> Result<String, ProvaraError> {
+    let mut data = Map::new();
+    data.insert("iss".to_string(), json!(delegation.iss));
+    data.insert("aud".to_string(), json!(delegation.aud));
+    data.insert("aud_public_key_b64".to_string(), json!(delegation.aud_public_key_b64));
+    data.insert(
+        "capabilities".to_string(),
+        serde_json::to_value(&delegation.capabilities).map_err(|e| ProvaraError::Serialization(e.to_string()))?,
+    );
+    if let Some(ref exp) = delegation.expires_at {
+        data.insert("expires_at".to_string(), json!(exp));
+    }
+    if let Some(ref proof) = delegation.proof {
+        data.insert("proof".to_string(), json!(proof));
+    }
+
+    let hash = crate::canonical_hash(&Value::Object(data))?;
+    Ok(format!("dlg_{}", hex::encode(&hash[0..12])))
+}
+
+/// Issue and sign a new delegation from `issuer` to `aud_public_key`.
+///
+/// `proof`, when present, is the `delegation_id` of the parent delegation
+/// this one attenuates; omit it only at the root of a chain, where `issuer`
+/// is itself the trusted authority over the granted resources.
+pub fn create_delegation(
+    issuer: &KeyPair,
+    aud_public_key: &PublicKeyBytes,
+    capabilities: Vec<Capability>,
+    expires_at: Option<String>,
+    proof: Option<String>,
+) -> Result<Delegation, ProvaraError> {
+    let PublicKeyBytes::Ed25519(aud_bytes) = aud_public_key else {
+        return Err(ProvaraError::Crypto("Delegations only support Ed25519 audience keys".to_string()));
+    };
+
+    use base64::Engine as _;
+    let mut delegation = Delegation {
+        delegation_id: String::new(),
+        iss: issuer.key_id()?,
+        aud: derive_key_id(aud_public_key)?,
+        aud_public_key_b64: base64::engine::general_purpose::STANDARD.encode(aud_bytes),
+        capabilities,
+        expires_at,
+        proof,
+        signature: None,
+    };
+
+    delegation.delegation_id = derive_delegation_id(&delegation)?;
+
+    let signing_payload = delegation.signing_payload()?;
+    let canonical_bytes = canonicalize(&signing_payload)?;
+    let hash = sha256_hash(&canonical_bytes);
+    let signature = issuer.sign(&hash)?;
+    delegation.signature = Some(base64::engine::general_purpose::STANDARD.encode(signature));
+
+    Ok(delegation)
+}
+
+/// Verify a delegation's own Ed25519 signature against its claimed issuer.
+pub fn verify_delegation_signature(delegation: &Delegation, issuer_public_key: &PublicKeyBytes) -> Result<bool, ProvaraError> {
+    let PublicKeyBytes::Ed25519(key_bytes) = issuer_public_key else {
+        return Err(ProvaraError::Crypto("Delegations only support Ed25519 issuer keys".to_string()));
+    };
+
+    let signature_b64 = delegation
+        .signature
+        .as_ref()
+        .ok_or_else(|| ProvaraError::InvalidEvent("Delegation missing signature".to_string()))?;
+
+    use base64::Engine as _;
+    let sig_bytes = base64::engine::general_purpose::STANDARD
+        .decode(signature_b64)
+        .map_err(|e| ProvaraError::Encoding(format!("Base64 decode failed: {}", e)))?;
+    let signature = Signature::from_slice(&sig_bytes)
+        .map_err(|e| ProvaraError::Crypto(format!("Invalid signature: {}", e)))?;
+    let verifying_key = VerifyingKey::from_bytes(key_bytes)
+        .map_err(|e| ProvaraError::Crypto(format!("Invalid public key: {}", e)))?;
+
+    let signing_payload = delegation.signing_payload()?;
+    let canonical_bytes = canonicalize(&signing_payload)?;
+    let hash = sha256_hash(&canonical_bytes);
+
+    verifying_key
+        .verify(&hash, &signature)
+        .map_err(|e| ProvaraError::Crypto(format!("Signature verification failed: {}", e)))?;
+
+    Ok(true)
+}
+
+/// Walk `leaf`'s proof chain (through `delegations`) back to a root issued by
+/// `root_authority`, checking at every link that:
+/// - the link's signature verifies against its claimed issuer,
+/// - the link is not expired as of `now_utc` (ISO 8601 UTC, compared lexically),
+/// - the parent's `aud` matches the child's `iss`, and
+/// - the child's capabilities attenuate (are a subset of) the parent's.
+pub fn verify_delegation_chain(
+    delegations: &[Delegation],
+    leaf: &Delegation,
+    root_authority: &PublicKeyBytes,
+    now_utc: &str,
+) -> Result<(), ProvaraError> {
+    let by_id: BTreeMap<&str, &Delegation> = delegations.iter().map(|d| (d.delegation_id.as_str(), d)).collect();
+    let root_key_id = derive_key_id(root_authority)?;
+
+    // Guard against a crafted proof chain that cycles back on itself (e.g.
+    // A.proof = B, B.proof = A), which would otherwise spin this loop
+    // forever since it only terminates on `proof: None`.
+    let mut visited: BTreeSet<&str> = BTreeSet::new();
+    visited.insert(leaf.delegation_id.as_str());
+
+    let mut current = leaf;
+    loop {
+        let issuer_public_key = match &current.proof {
+            None => {
+                if current.iss != root_key_id {
+                    return Err(ProvaraError::ChainValidation(format!(
+                        "root delegation {} issuer {} does not match trusted root authority {}",
+                        current.delegation_id, current.iss, root_key_id
+                    )));
+                }
+                root_authority.clone()
+            }
+            Some(proof_id) => {
+                if !visited.insert(proof_id.as_str()) {
+                    return Err(ProvaraError::ChainValidation(format!(
+                        "delegation chain contains a cycle at proof {}",
+                        proof_id
+                    )));
+                }
+                let parent = by_id.get(proof_id.as_str()).ok_or_else(|| {
+                    ProvaraError::ChainValidation(format!("missing proof delegation {}", proof_id))
+                })?;
+
+                if parent.aud != current.iss {
+                    return Err(ProvaraError::ChainValidation(format!(
+                        "delegation {} issuer {} does not match proof's audience {}",
+                        current.delegation_id, current.iss, parent.aud
+                    )));
+                }
+
+                for cap in &current.capabilities {
+                    if !parent.capabilities.iter().any(|parent_cap| cap.attenuates(parent_cap)) {
+                        return Err(ProvaraError::ChainValidation(format!(
+                            "delegation {} grants a capability not held by its proof {}",
+                            current.delegation_id, parent.delegation_id
+                        )));
+                    }
+                }
+
+                use base64::Engine as _;
+                let bytes = base64::engine::general_purpose::STANDARD
+                    .decode(&parent.aud_public_key_b64)
+                    .map_err(|e| ProvaraError::Encoding(format!("invalid aud_public_key_b64: {}", e)))?;
+                if bytes.len() != 32 {
+                    return Err(ProvaraError::KeyDerivation("aud_public_key_b64 must be 32 bytes".to_string()));
+                }
+                let mut key = [0u8; 32];
+                key.copy_from_slice(&bytes);
+                PublicKeyBytes::Ed25519(key)
+            }
+        };
+
+        if !verify_delegation_signature(current, &issuer_public_key)? {
+            return Err(ProvaraError::ChainValidation(format!(
+                "delegation {} has an invalid signature",
+                current.delegation_id
+            )));
+        }
+
+        if let Some(expires_at) = &current.expires_at {
+            if now_utc >= expires_at.as_str() {
+                return Err(ProvaraError::ChainValidation(format!(
+                    "delegation {} expired at {}",
+                    current.delegation_id, expires_at
+                )));
+            }
+        }
+
+        match &current.proof {
+            None => break,
+            Some(proof_id) => {
+                current = by_id.get(proof_id.as_str()).expect("looked up above");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Confirm that `event.actor` holds, via a valid delegation chain rooted at
+/// `root_authority`, a capability covering this event's `subject:predicate`
+/// and the action implied by its `type`.
+pub fn verify_event_authorized(
+    event: &Event,
+    delegations: &[Delegation],
+    root_authority: &PublicKeyBytes,
+    now_utc: &str,
+) -> Result<bool, ProvaraError> {
+    let action = match event.event_type.as_str() {
+        "OBSERVATION" => Action::Observe,
+        "ASSERTION" => Action::Assert,
+        "ATTESTATION" => Action::Attest,
+        "RETRACTION" => Action::Retract,
+        other => {
+            return Err(ProvaraError::InvalidEvent(format!(
+                "no authorization semantics defined for event type {}",
+                other
+            )))
+        }
+    };
+
+    let subject = event.payload.get("subject").and_then(|v| v.as_str()).ok_or_else(|| {
+        ProvaraError::InvalidEvent("event payload missing subject".to_string())
+    })?;
+    let predicate = event.payload.get("predicate").and_then(|v| v.as_str()).ok_or_else(|| {
+        ProvaraError::InvalidEvent("event payload missing predicate".to_string())
+    })?;
+    let resource = format!("{}:{}", subject, predicate);
+
+    let leaf = delegations
+        .iter()
+        .find(|d| d.aud == event.actor && d.capabilities.iter().any(|c| c.permits(&resource, action)))
+        .ok_or_else(|| {
+            ProvaraError::InvalidEvent(format!("actor {} holds no capability over {}", event.actor, resource))
+        })?;
+
+    verify_delegation_chain(delegations, leaf, root_authority, now_utc)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    fn actions(list: &[Action]) -> BTreeSet<Action> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_delegation_round_trip() {
+        let mut rng = thread_rng();
+        let root = KeyPair::generate(&mut rng);
+        let alice = KeyPair::generate(&mut rng);
+
+        let delegation = create_delegation(
+            &root,
+            &alice.public_key(),
+            vec![Capability { resource: "door:*".to_string(), actions: actions(&[Action::Assert]) }],
+            None,
+            None,
+        ).unwrap();
+
+        assert!(delegation.delegation_id.starts_with("dlg_"));
+        assert!(verify_delegation_signature(&delegation, &root.public_key()).unwrap());
+    }
+
+    #[test]
+    fn test_chain_attenuation_enforced() {
+        let mut rng = thread_rng();
+        let root = KeyPair::generate(&mut rng);
+        let alice = KeyPair::generate(&mut rng);
+        let bob = KeyPair::generate(&mut rng);
+
+        let root_delegation = create_delegation(
+            &root,
+            &alice.public_key(),
+            vec![Capability { resource: "door:*".to_string(), actions: actions(&[Action::Assert, Action::Attest]) }],
+            None,
+            None,
+        ).unwrap();
+
+        // Alice attempts to grant Bob a capability she doesn't hold (RETRACT).
+        let over_broad = create_delegation(
+            &alice,
+            &bob.public_key(),
+            vec![Capability { resource: "door:status".to_string(), actions: actions(&[Action::Retract]) }],
+            None,
+            Some(root_delegation.delegation_id.clone()),
+        ).unwrap();
+
+        let chain = vec![root_delegation, over_broad.clone()];
+        let result = verify_delegation_chain(&chain, &over_broad, &root.public_key(), "2026-01-01T00:00:00Z");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_verify_delegation_chain_rejects_proof_cycle() {
+        // delegation_id is signed but never re-derived from the delegation's
+        // content during verification, so a crafted pair of delegations can
+        // each claim the other as their proof even though neither is a
+        // legitimate root; the cycle must be rejected rather than spinning
+        // the verification loop forever.
+        let mut rng = thread_rng();
+        let root = KeyPair::generate(&mut rng);
+        let alice = KeyPair::generate(&mut rng);
+        let bob = KeyPair::generate(&mut rng);
+
+        use base64::Engine as _;
+        let PublicKeyBytes::Ed25519(alice_bytes) = alice.public_key() else { panic!("expected Ed25519 key") };
+        let PublicKeyBytes::Ed25519(bob_bytes) = bob.public_key() else { panic!("expected Ed25519 key") };
+        let cap = vec![Capability { resource: "door:*".to_string(), actions: actions(&[Action::Assert]) }];
+
+        let mut a = Delegation {
+            delegation_id: "dlg_a".to_string(),
+            iss: alice.key_id().unwrap(),
+            aud: bob.key_id().unwrap(),
+            aud_public_key_b64: base64::engine::general_purpose::STANDARD.encode(bob_bytes),
+            capabilities: cap.clone(),
+            expires_at: None,
+            proof: Some("dlg_b".to_string()),
+            signature: None,
+        };
+        let hash = sha256_hash(&canonicalize(&a.signing_payload().unwrap()).unwrap());
+        a.signature = Some(base64::engine::general_purpose::STANDARD.encode(alice.sign(&hash).unwrap()));
+
+        let mut b = Delegation {
+            delegation_id: "dlg_b".to_string(),
+            iss: bob.key_id().unwrap(),
+            aud: alice.key_id().unwrap(),
+            aud_public_key_b64: base64::engine::general_purpose::STANDARD.encode(alice_bytes),
+            capabilities: cap,
+            expires_at: None,
+            proof: Some("dlg_a".to_string()),
+            signature: None,
+        };
+        let hash = sha256_hash(&canonicalize(&b.signing_payload().unwrap()).unwrap());
+        b.signature = Some(base64::engine::general_purpose::STANDARD.encode(bob.sign(&hash).unwrap()));
+
+        let chain = vec![a.clone(), b];
+        let result = verify_delegation_chain(&chain, &a, &root.public_key(), "2026-01-01T00:00:00Z");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_event_authorization() {
+        let mut rng = thread_rng();
+        let root = KeyPair::generate(&mut rng);
+        let alice = KeyPair::generate(&mut rng);
+
+        let delegation = create_delegation(
+            &root,
+            &alice.public_key(),
+            vec![Capability { resource: "door:status".to_string(), actions: actions(&[Action::Attest]) }],
+            None,
+            None,
+        ).unwrap();
+
+        let event = crate::create_event(
+            "ATTESTATION",
+            &alice,
+            None,
+            json!({"subject": "door", "predicate": "status", "value": "locked"}),
+        ).unwrap();
+
+        assert!(verify_event_authorized(&event, &[delegation], &root.public_key(), "2026-01-01T00:00:00Z").unwrap());
+    }
+}
@@ -0,0 +1,295 @@
+//! JWK import/export and a JWS envelope for Provara events.
+//!
+//! Provara events are self-contained, Ed25519-signed JSON objects, but that
+//! format is opaque to generic W3C Verifiable-Credential / JOSE tooling.
+//! This module lets an Ed25519 public key travel as an `OKP` JWK or a
+//! `did:key` string, and lets a Provara event travel as a compact JWS whose
+//! payload is the event's own canonical JSON — so a generic JWS verifier can
+//! check authenticity while the inner `event_id`/`signature` remain intact
+//! for `verify_event_signature`.
+
+use crate::{canonicalize, Event, KeyPair, PublicKeyBytes, ProvaraError};
+use base64::Engine as _;
+use ed25519_dalek::{Signature, Signer, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Multicodec prefix for an Ed25519 public key (0xed, varint-encoded).
+const MULTICODEC_ED25519_PUB: [u8; 2] = [0xed, 0x01];
+
+fn b64url(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn b64url_decode(s: &str) -> Result<Vec<u8>, ProvaraError> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(s)
+        .map_err(|e| ProvaraError::Encoding(format!("base64url decode failed: {}", e)))
+}
+
+/// An `OKP`/`Ed25519` JSON Web Key, per RFC 8037.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    /// base64url-encoded public key point
+    pub x: String,
+    /// base64url-encoded private seed, present only when exporting a keypair
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+}
+
+/// Export an Ed25519 public key as an `OKP` JWK.
+pub fn public_key_to_jwk(public_key: &PublicKeyBytes) -> Result<Jwk, ProvaraError> {
+    let PublicKeyBytes::Ed25519(bytes) = public_key else {
+        return Err(ProvaraError::Crypto(
+            "JWK export only supports Ed25519 public keys".to_string(),
+        ));
+    };
+
+    Ok(Jwk {
+        kty: "OKP".to_string(),
+        crv: "Ed25519".to_string(),
+        x: b64url(bytes),
+        d: None,
+    })
+}
+
+/// Export an Ed25519 keypair (public + private) as an `OKP` JWK.
+pub fn keypair_to_jwk(keypair: &KeyPair) -> Result<Jwk, ProvaraError> {
+    let mut jwk = public_key_to_jwk(&keypair.public_key())?;
+    jwk.d = Some(b64url(&keypair.seed_bytes()));
+    Ok(jwk)
+}
+
+/// Recover an Ed25519 public key from an `OKP` JWK.
+pub fn jwk_to_public_key(jwk: &Jwk) -> Result<PublicKeyBytes, ProvaraError> {
+    if jwk.kty != "OKP" || jwk.crv != "Ed25519" {
+        return Err(ProvaraError::Crypto(format!(
+            "Unsupported JWK kty/crv: {}/{}",
+            jwk.kty, jwk.crv
+        )));
+    }
+
+    let bytes = b64url_decode(&jwk.x)?;
+    if bytes.len() != 32 {
+        return Err(ProvaraError::KeyDerivation(format!(
+            "JWK x must decode to 32 bytes, got {}",
+            bytes.len()
+        )));
+    }
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&bytes);
+    Ok(PublicKeyBytes::Ed25519(key))
+}
+
+/// Recover an Ed25519 keypair from an `OKP` JWK that carries a private `d`.
+pub fn jwk_to_keypair(jwk: &Jwk) -> Result<KeyPair, ProvaraError> {
+    let d = jwk
+        .d
+        .as_ref()
+        .ok_or_else(|| ProvaraError::KeyDerivation("JWK has no private key material (d)".to_string()))?;
+    let bytes = b64url_decode(d)?;
+    if bytes.len() != 32 {
+        return Err(ProvaraError::KeyDerivation(format!(
+            "JWK d must decode to 32 bytes, got {}",
+            bytes.len()
+        )));
+    }
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&bytes);
+    KeyPair::from_bytes(&seed)
+}
+
+/// Derive a `did:key` string from an Ed25519 public key.
+///
+/// `did:key:<multibase(base58btc, multicodec(0xed01) || raw_public_key)>`
+pub fn did_key_from_public_key(public_key: &PublicKeyBytes) -> Result<String, ProvaraError> {
+    let PublicKeyBytes::Ed25519(bytes) = public_key else {
+        return Err(ProvaraError::Crypto(
+            "did:key derivation only supports Ed25519 public keys".to_string(),
+        ));
+    };
+
+    let mut prefixed = Vec::with_capacity(2 + bytes.len());
+    prefixed.extend_from_slice(&MULTICODEC_ED25519_PUB);
+    prefixed.extend_from_slice(bytes);
+
+    Ok(format!("did:key:z{}", bs58::encode(prefixed).into_string()))
+}
+
+/// Recover an Ed25519 public key from a `did:key` string.
+pub fn did_key_to_public_key(did: &str) -> Result<PublicKeyBytes, ProvaraError> {
+    let multibase = did
+        .strip_prefix("did:key:")
+        .ok_or_else(|| ProvaraError::KeyDerivation(format!("Not a did:key: {}", did)))?;
+    let encoded = multibase
+        .strip_prefix('z')
+        .ok_or_else(|| ProvaraError::KeyDerivation("did:key must use base58btc ('z') multibase".to_string()))?;
+
+    let decoded = bs58::decode(encoded)
+        .into_vec()
+        .map_err(|e| ProvaraError::Encoding(format!("invalid base58btc in did:key: {}", e)))?;
+
+    if decoded.len() != 2 + 32 || decoded[..2] != MULTICODEC_ED25519_PUB {
+        return Err(ProvaraError::KeyDerivation(
+            "did:key is not an Ed25519 (0xed01) multicodec public key".to_string(),
+        ));
+    }
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&decoded[2..]);
+    Ok(PublicKeyBytes::Ed25519(key))
+}
+
+/// Wrap an existing, already-signed Provara event as a compact JWS.
+///
+/// Header: `{"alg":"EdDSA","kid":<did:key>}`. Payload: the canonical JSON of
+/// the event exactly as produced by `create_event_full`, including its own
+/// `event_id` and `signature` — the JWS signature is an outer envelope, not
+/// a replacement for the event's native signature.
+pub fn event_to_jws(event: &Event, keypair: &KeyPair) -> Result<String, ProvaraError> {
+    let kid = did_key_from_public_key(&keypair.public_key())?;
+    let header = json!({"alg": "EdDSA", "kid": kid});
+    let header_b64 = b64url(&canonicalize(&header)?);
+
+    let event_value = serde_json::to_value(event)
+        .map_err(|e| ProvaraError::Serialization(e.to_string()))?;
+    let payload_b64 = b64url(&canonicalize(&event_value)?);
+
+    let signing_input = format!("{}.{}", header_b64, payload_b64);
+    let signature_bytes = keypair.sign(signing_input.as_bytes())?;
+
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        b64url(&signature_bytes)
+    ))
+}
+
+/// Unwrap a compact JWS produced by `event_to_jws`, verifying the outer
+/// EdDSA signature against the `kid` (a `did:key`) in its header, and return
+/// the inner Provara event.
+pub fn verify_jws(jws: &str) -> Result<Event, ProvaraError> {
+    let mut parts = jws.split('.');
+    let header_b64 = parts
+        .next()
+        .ok_or_else(|| ProvaraError::InvalidEvent("JWS missing header segment".to_string()))?;
+    let payload_b64 = parts
+        .next()
+        .ok_or_else(|| ProvaraError::InvalidEvent("JWS missing payload segment".to_string()))?;
+    let signature_b64 = parts
+        .next()
+        .ok_or_else(|| ProvaraError::InvalidEvent("JWS missing signature segment".to_string()))?;
+    if parts.next().is_some() {
+        return Err(ProvaraError::InvalidEvent("JWS has too many segments".to_string()));
+    }
+
+    let header: serde_json::Value = serde_json::from_slice(&b64url_decode(header_b64)?)
+        .map_err(|e| ProvaraError::Serialization(format!("invalid JWS header: {}", e)))?;
+
+    let alg = header.get("alg").and_then(|v| v.as_str()).unwrap_or("");
+    if alg != "EdDSA" {
+        return Err(ProvaraError::InvalidEvent(format!("Unsupported JWS alg: {}", alg)));
+    }
+    let kid = header
+        .get("kid")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ProvaraError::InvalidEvent("JWS header missing kid".to_string()))?;
+
+    let public_key = did_key_to_public_key(kid)?;
+    let PublicKeyBytes::Ed25519(key_bytes) = public_key else {
+        return Err(ProvaraError::Crypto("JWS kid did not resolve to an Ed25519 key".to_string()));
+    };
+    let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+        .map_err(|e| ProvaraError::Crypto(format!("Invalid public key in kid: {}", e)))?;
+
+    let signing_input = format!("{}.{}", header_b64, payload_b64);
+    let signature_bytes = b64url_decode(signature_b64)?;
+    let signature = Signature::from_slice(&signature_bytes)
+        .map_err(|e| ProvaraError::Crypto(format!("Invalid JWS signature: {}", e)))?;
+
+    verifying_key
+        .verify(signing_input.as_bytes(), &signature)
+        .map_err(|e| ProvaraError::Crypto(format!("JWS signature verification failed: {}", e)))?;
+
+    let event: Event = serde_json::from_slice(&b64url_decode(payload_b64)?)
+        .map_err(|e| ProvaraError::Serialization(format!("invalid JWS payload: {}", e)))?;
+
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_event, verify_event_signature};
+    use rand::thread_rng;
+    use serde_json::json;
+
+    #[test]
+    fn test_jwk_round_trip() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let jwk = keypair_to_jwk(&keypair).unwrap();
+        assert_eq!(jwk.kty, "OKP");
+        assert_eq!(jwk.crv, "Ed25519");
+
+        let recovered_pub = jwk_to_public_key(&jwk).unwrap();
+        assert_eq!(recovered_pub, keypair.public_key());
+
+        let recovered_keypair = jwk_to_keypair(&jwk).unwrap();
+        assert_eq!(recovered_keypair.seed_bytes(), keypair.seed_bytes());
+    }
+
+    #[test]
+    fn test_did_key_round_trip() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let did = did_key_from_public_key(&keypair.public_key()).unwrap();
+        assert!(did.starts_with("did:key:z"));
+
+        let recovered = did_key_to_public_key(&did).unwrap();
+        assert_eq!(recovered, keypair.public_key());
+    }
+
+    #[test]
+    fn test_jws_round_trip_preserves_native_signature() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let event = create_event(
+            "OBSERVATION",
+            &keypair,
+            None,
+            json!({"subject": "door", "predicate": "status", "value": "open"}),
+        ).unwrap();
+
+        let jws = event_to_jws(&event, &keypair).unwrap();
+        assert_eq!(jws.split('.').count(), 3);
+
+        let unwrapped = verify_jws(&jws).unwrap();
+        assert_eq!(unwrapped.event_id, event.event_id);
+        assert_eq!(unwrapped.signature, event.signature);
+
+        // The native Provara signature still verifies after JWS unwrap.
+        assert!(verify_event_signature(&unwrapped, &keypair.public_key()).unwrap());
+    }
+
+    #[test]
+    fn test_jws_rejects_tampered_payload() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let event = create_event("OBSERVATION", &keypair, None, json!({"value": 1})).unwrap();
+        let jws = event_to_jws(&event, &keypair).unwrap();
+
+        let mut segments: Vec<&str> = jws.split('.').collect();
+        let tampered_payload = b64url(br#"{"tampered":true}"#);
+        segments[1] = &tampered_payload;
+        let tampered = segments.join(".");
+
+        assert!(verify_jws(&tampered).is_err());
+    }
+}
@@ -3,7 +3,7 @@
 //! This crate provides the core cryptographic primitives and data structures
 //! for the Provara Protocol, including:
 //!
-//! - Ed25519 signing and verification (RFC 8032)
+//! - Algorithm-agile signing and verification (Ed25519 per RFC 8032, ES256K via secp256k1)
 //! - SHA-256 hashing (FIPS 180-4)
 //! - Event creation and validation
 //! - Causal chain verification
@@ -35,10 +35,13 @@
 use wasm_bindgen::prelude::*;
 
 use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
 use rand_core::{CryptoRng, RngCore};
 use sha2::{Digest, Sha256};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, Map, json};
+use std::str::FromStr;
 use thiserror::Error;
 
 pub use jcs_rs::{canonicalize, canonical_to_string, canonical_hash, canonical_hash_hex};
@@ -46,6 +49,24 @@ pub use jcs_rs::{canonicalize, canonical_to_string, canonical_hash, canonical_ha
 pub mod reducer;
 pub use reducer::{SovereignReducerV0, ReducerState, ReducerMetadata, StateMetadata};
 
+pub mod jwk;
+pub use jwk::{
+    did_key_from_public_key, did_key_to_public_key, event_to_jws, jwk_to_keypair,
+    jwk_to_public_key, keypair_to_jwk, public_key_to_jwk, verify_jws, Jwk,
+};
+
+pub mod delegation;
+pub use delegation::{
+    create_delegation, verify_delegation_chain, verify_delegation_signature,
+    verify_event_authorized, Action, Capability, Delegation,
+};
+
+pub mod cbor;
+pub use cbor::{event_from_dcbor, event_to_dcbor};
+
+pub mod store;
+pub use store::{verify_chain_from, EventStore, InMemoryEventStore};
+
 #[cfg(test)]
 mod test_vectors;
 #[cfg(test)]
@@ -74,68 +95,267 @@ impl From<jcs_rs::CanonicalizeError> for ProvaraError {
     }
 }
 
-/// A public/private keypair for Ed25519 signing
-pub struct KeyPair {
-    signing_key: SigningKey,
-    verifying_key: VerifyingKey,
+/// Signature suite used to sign and verify a Provara event.
+///
+/// Events omit the `alg` field entirely when they are Ed25519 (the original,
+/// and still default, suite) so that pre-existing events and test vectors
+/// keep hashing and verifying exactly as before. Any other suite must be
+/// named explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Algorithm {
+    #[serde(rename = "Ed25519")]
+    Ed25519,
+    #[serde(rename = "ES256K")]
+    Es256k,
+}
+
+impl Algorithm {
+    /// The `alg` string as it appears on the wire.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::Ed25519 => "Ed25519",
+            Algorithm::Es256k => "ES256K",
+        }
+    }
+
+    /// The key_id prefix for this suite. Distinct prefixes prevent a
+    /// secp256k1 key and an Ed25519 key from ever colliding on key_id.
+    pub fn key_id_prefix(&self) -> &'static str {
+        match self {
+            Algorithm::Ed25519 => "bp1_",
+            Algorithm::Es256k => "bp1k_",
+        }
+    }
+}
+
+impl Default for Algorithm {
+    fn default() -> Self {
+        Algorithm::Ed25519
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = ProvaraError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Ed25519" => Ok(Algorithm::Ed25519),
+            "ES256K" => Ok(Algorithm::Es256k),
+            other => Err(ProvaraError::Crypto(format!("Unknown algorithm: {}", other))),
+        }
+    }
+}
+
+/// Public key bytes for a specific signature suite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicKeyBytes {
+    /// Raw 32-byte Ed25519 point.
+    Ed25519([u8; 32]),
+    /// SEC1-compressed secp256k1 point (33 bytes).
+    Es256k([u8; 33]),
+}
+
+impl PublicKeyBytes {
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            PublicKeyBytes::Ed25519(_) => Algorithm::Ed25519,
+            PublicKeyBytes::Es256k(_) => Algorithm::Es256k,
+        }
+    }
+
+    /// The raw bytes as they should be hashed for key_id derivation and
+    /// as they would be transmitted on the wire.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            PublicKeyBytes::Ed25519(b) => b,
+            PublicKeyBytes::Es256k(b) => b,
+        }
+    }
+}
+
+/// A public/private keypair, backed by one of the supported signature suites.
+pub enum KeyPair {
+    Ed25519 {
+        signing_key: SigningKey,
+        verifying_key: VerifyingKey,
+    },
+    Es256k {
+        signing_key: k256::ecdsa::SigningKey,
+        verifying_key: k256::ecdsa::VerifyingKey,
+    },
 }
 
 impl KeyPair {
-    /// Generate a new random keypair
+    /// Generate a new random Ed25519 keypair (the default suite).
     pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
-        let signing_key = SigningKey::generate(rng);
-        let verifying_key = VerifyingKey::from(&signing_key);
-        
-        KeyPair {
-            signing_key,
-            verifying_key,
+        Self::generate_with_algorithm(Algorithm::Ed25519, rng)
+    }
+
+    /// Generate a new random keypair under the given signature suite.
+    pub fn generate_with_algorithm<R: CryptoRng + RngCore>(alg: Algorithm, rng: &mut R) -> Self {
+        match alg {
+            Algorithm::Ed25519 => {
+                let signing_key = SigningKey::generate(rng);
+                let verifying_key = VerifyingKey::from(&signing_key);
+                KeyPair::Ed25519 { signing_key, verifying_key }
+            }
+            Algorithm::Es256k => {
+                let signing_key = k256::ecdsa::SigningKey::random(rng);
+                let verifying_key = *signing_key.verifying_key();
+                KeyPair::Es256k { signing_key, verifying_key }
+            }
         }
     }
-    
-    /// Create a keypair from raw bytes
+
+    /// Create an Ed25519 keypair from a raw 32-byte seed (back-compat path).
     pub fn from_bytes(seed: &[u8; 32]) -> Result<Self, ProvaraError> {
-        let signing_key = SigningKey::from_bytes(seed);
-        let verifying_key = VerifyingKey::from(&signing_key);
-        
-        Ok(KeyPair {
-            signing_key,
-            verifying_key,
-        })
+        Self::from_bytes_with_algorithm(Algorithm::Ed25519, seed)
     }
-    
+
+    /// Create a keypair from raw key material under the given suite.
+    ///
+    /// Both suites take a 32-byte scalar: the Ed25519 seed, or the
+    /// secp256k1 private scalar.
+    pub fn from_bytes_with_algorithm(alg: Algorithm, seed: &[u8]) -> Result<Self, ProvaraError> {
+        match alg {
+            Algorithm::Ed25519 => {
+                if seed.len() != 32 {
+                    return Err(ProvaraError::KeyDerivation(format!(
+                        "Ed25519 seed must be 32 bytes, got {}",
+                        seed.len()
+                    )));
+                }
+                let mut buf = [0u8; 32];
+                buf.copy_from_slice(seed);
+                let signing_key = SigningKey::from_bytes(&buf);
+                let verifying_key = VerifyingKey::from(&signing_key);
+                Ok(KeyPair::Ed25519 { signing_key, verifying_key })
+            }
+            Algorithm::Es256k => {
+                let signing_key = k256::ecdsa::SigningKey::from_slice(seed)
+                    .map_err(|e| ProvaraError::KeyDerivation(format!("Invalid secp256k1 scalar: {}", e)))?;
+                let verifying_key = *signing_key.verifying_key();
+                Ok(KeyPair::Es256k { signing_key, verifying_key })
+            }
+        }
+    }
+
+    /// The signature suite this keypair was created under.
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            KeyPair::Ed25519 { .. } => Algorithm::Ed25519,
+            KeyPair::Es256k { .. } => Algorithm::Es256k,
+        }
+    }
+
     /// Get the public key bytes
-    pub fn public_key(&self) -> [u8; 32] {
-        self.verifying_key.to_bytes()
+    pub fn public_key(&self) -> PublicKeyBytes {
+        match self {
+            KeyPair::Ed25519 { verifying_key, .. } => PublicKeyBytes::Ed25519(verifying_key.to_bytes()),
+            KeyPair::Es256k { verifying_key, .. } => {
+                let point = verifying_key.to_encoded_point(true);
+                let mut buf = [0u8; 33];
+                buf.copy_from_slice(point.as_bytes());
+                PublicKeyBytes::Es256k(buf)
+            }
+        }
     }
-    
-    /// Get the key ID (bp1_ prefix + first 16 hex chars of SHA-256(public_key))
+
+    /// Get the key ID (suite-specific prefix + first 16 hex chars of SHA-256(public_key))
     pub fn key_id(&self) -> Result<String, ProvaraError> {
         derive_key_id(&self.public_key())
     }
-    
-    /// Sign a message
-    pub fn sign(&self, message: &[u8]) -> Signature {
-        self.signing_key.sign(message)
+
+    /// Sign a pre-hashed message, returning the raw signature bytes.
+    ///
+    /// Both suites produce a fixed 64-byte `r || s` signature, so callers
+    /// don't need to special-case the suite when encoding it.
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>, ProvaraError> {
+        match self {
+            KeyPair::Ed25519 { signing_key, .. } => Ok(signing_key.sign(message).to_bytes().to_vec()),
+            KeyPair::Es256k { signing_key, .. } => {
+                let sig: k256::ecdsa::Signature = signing_key
+                    .sign_prehash(message)
+                    .map_err(|e| ProvaraError::Crypto(format!("secp256k1 signing failed: {}", e)))?;
+                // Low-S normalization avoids signature malleability.
+                let sig = sig.normalize_s().unwrap_or(sig);
+                Ok(sig.to_bytes().to_vec())
+            }
+        }
     }
 
-    /// Get the raw seed bytes (32-byte private key material)
+    /// Get the raw seed/scalar bytes (32-byte private key material)
     pub fn seed_bytes(&self) -> [u8; 32] {
-        self.signing_key.to_bytes()
+        match self {
+            KeyPair::Ed25519 { signing_key, .. } => signing_key.to_bytes(),
+            KeyPair::Es256k { signing_key, .. } => {
+                let mut buf = [0u8; 32];
+                buf.copy_from_slice(&signing_key.to_bytes());
+                buf
+            }
+        }
+    }
+
+    /// Deterministically derive an Ed25519 keypair from a memorized
+    /// passphrase ("brain wallet"), via Argon2id with fixed parameters and a
+    /// domain-separation salt. The same passphrase always reconstructs the
+    /// same identity; a weak passphrase is still only as strong as Argon2id
+    /// makes it, so this is a recovery mechanism, not a substitute for a
+    /// properly random key.
+    pub fn from_passphrase(passphrase: &str) -> Result<Self, ProvaraError> {
+        use argon2::{Algorithm as Argon2Algorithm, Argon2, Params, Version};
+
+        const DOMAIN_SALT: &[u8] = b"provara-bp1";
+
+        let params = Params::new(19 * 1024, 2, 1, Some(32))
+            .map_err(|e| ProvaraError::KeyDerivation(format!("invalid Argon2id params: {}", e)))?;
+        let argon2 = Argon2::new(Argon2Algorithm::Argon2id, Version::V0x13, params);
+
+        let mut seed = [0u8; 32];
+        argon2
+            .hash_password_into(passphrase.as_bytes(), DOMAIN_SALT, &mut seed)
+            .map_err(|e| ProvaraError::KeyDerivation(format!("Argon2id derivation failed: {}", e)))?;
+
+        Self::from_bytes(&seed)
+    }
+
+    /// Search for an Ed25519 keypair whose key_id's hex portion starts with
+    /// `prefix`, trying up to `max_attempts` random keypairs.
+    ///
+    /// Cost grows ~16x per additional hex character in `prefix` (each hex
+    /// digit narrows the search space by a factor of 16), so anything past
+    /// a handful of characters quickly becomes infeasible.
+    pub fn mine_vanity(prefix: &str, max_attempts: u64) -> Option<Self> {
+        if prefix.is_empty() || prefix.len() > 8 || !prefix.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()) {
+            return None;
+        }
+
+        use rand_core::OsRng;
+        for _ in 0..max_attempts {
+            let candidate = Self::generate(&mut OsRng);
+            if let Ok(key_id) = candidate.key_id() {
+                let hex_part = key_id.strip_prefix(Algorithm::Ed25519.key_id_prefix()).unwrap_or("");
+                if hex_part.starts_with(prefix) {
+                    return Some(candidate);
+                }
+            }
+        }
+        None
     }
 }
 
 /// Derive a key ID from public key bytes according to Provara spec
 ///
-/// key_id = "bp1_" + SHA-256(raw_public_key_bytes)[:16 hex chars]
-pub fn derive_key_id(public_key_bytes: &[u8; 32]) -> Result<String, ProvaraError> {
+/// key_id = <suite_prefix> + SHA-256(raw_public_key_bytes)[:16 hex chars]
+pub fn derive_key_id(public_key: &PublicKeyBytes) -> Result<String, ProvaraError> {
     let mut hasher = Sha256::new();
-    hasher.update(public_key_bytes);
+    hasher.update(public_key.as_bytes());
     let hash = hasher.finalize();
-    
+
     // Take first 8 bytes (16 hex chars)
     let hex_chars = hex::encode(&hash[0..8]);
-    
-    Ok(format!("bp1_{}", hex_chars))
+
+    Ok(format!("{}{}", public_key.algorithm().key_id_prefix(), hex_chars))
 }
 
 /// Compute SHA-256 hash of bytes
@@ -170,11 +390,23 @@ pub struct Event {
     /// Hash of previous event by same actor (null for genesis)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prev_event_hash: Option<String>,
-    
+
     /// Event timestamp (ISO 8601 UTC)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp_utc: Option<String>,
-    
+
+    /// Signature suite used for `signature` (e.g. "Ed25519", "ES256K").
+    /// Omitted for Ed25519, the original and default suite, so existing
+    /// events and test vectors are unaffected.
+    #[serde(rename = "alg", skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+
+    /// Monotonic per-actor sequence number (genesis = 1). Optional: actors
+    /// that don't set it are still checked on `prev_event_hash` linkage
+    /// alone by `verify_causal_chain_detailed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u64>,
+
     /// Event payload
     pub payload: Value,
     
@@ -196,29 +428,39 @@ impl Event {
             event_id: String::new(), // Will be computed
             actor: actor.to_string(),
             prev_event_hash,
-            payload,
             timestamp_utc: None,
+            alg: None,
+            sequence: None,
+            payload,
             signature: None,
         }
     }
-    
+
     /// Get the signing payload (event without signature field)
     pub fn signing_payload(&self) -> Result<Value, ProvaraError> {
         let mut map = Map::new();
         map.insert("type".to_string(), json!(self.event_type));
         map.insert("event_id".to_string(), json!(self.event_id));
         map.insert("actor".to_string(), json!(self.actor));
-        
+
         if let Some(ref prev) = self.prev_event_hash {
             map.insert("prev_event_hash".to_string(), json!(prev));
         }
-        
+
         if let Some(ref ts) = self.timestamp_utc {
             map.insert("timestamp_utc".to_string(), json!(ts));
         }
-        
+
+        if let Some(ref alg) = self.alg {
+            map.insert("alg".to_string(), json!(alg));
+        }
+
+        if let Some(seq) = self.sequence {
+            map.insert("sequence".to_string(), json!(seq));
+        }
+
         map.insert("payload".to_string(), self.payload.clone());
-        
+
         Ok(Value::Object(map))
     }
 }
@@ -239,7 +481,15 @@ pub fn derive_event_id(event: &Event) -> Result<String, ProvaraError> {
     if let Some(ref ts) = event.timestamp_utc {
         event_data.insert("timestamp_utc".to_string(), json!(ts));
     }
-    
+
+    if let Some(ref alg) = event.alg {
+        event_data.insert("alg".to_string(), json!(alg));
+    }
+
+    if let Some(seq) = event.sequence {
+        event_data.insert("sequence".to_string(), json!(seq));
+    }
+
     event_data.insert("payload".to_string(), event.payload.clone());
     
     let value = Value::Object(event_data);
@@ -263,6 +513,12 @@ pub fn create_event_full(
 
     let mut event = Event::new(event_type, &actor, prev_event_hash, payload);
     event.timestamp_utc = timestamp_utc;
+    // Ed25519 stays implicit so pre-existing events and test vectors are
+    // unaffected; any other suite must declare itself.
+    event.alg = match keypair.algorithm() {
+        Algorithm::Ed25519 => None,
+        other => Some(other.as_str().to_string()),
+    };
 
     // Compute event_id
     event.event_id = derive_event_id(&event)?;
@@ -275,11 +531,11 @@ pub fn create_event_full(
     let hash = sha256_hash(&canonical_bytes);
 
     // Sign the hash
-    let signature = keypair.sign(&hash);
+    let signature = keypair.sign(&hash)?;
 
     // Encode signature as Base64
     use base64::Engine as _;
-    event.signature = Some(base64::engine::general_purpose::STANDARD.encode(signature.to_bytes()));
+    event.signature = Some(base64::engine::general_purpose::STANDARD.encode(signature));
 
     Ok(event)
 }
@@ -295,39 +551,68 @@ pub fn create_event(
 }
 
 /// Verify an event's signature
-pub fn verify_event_signature(event: &Event, public_key: &[u8; 32]) -> Result<bool, ProvaraError> {
+///
+/// The event's declared `alg` (Ed25519 when absent) must match the suite of
+/// `public_key`; otherwise the event is rejected outright to prevent
+/// cross-suite key confusion.
+pub fn verify_event_signature(event: &Event, public_key: &PublicKeyBytes) -> Result<bool, ProvaraError> {
+    let event_alg = match event.alg.as_deref() {
+        Some(s) => Algorithm::from_str(s)?,
+        None => Algorithm::Ed25519,
+    };
+
+    if event_alg != public_key.algorithm() {
+        return Err(ProvaraError::InvalidEvent(format!(
+            "Event alg {} does not match verifying key suite {}",
+            event_alg.as_str(),
+            public_key.algorithm().as_str()
+        )));
+    }
+
     let signature_b64 = event.signature.as_ref()
         .ok_or_else(|| ProvaraError::InvalidEvent("Missing signature".to_string()))?;
-    
+
     // Decode Base64 signature
     use base64::Engine as _;
     let sig_bytes = base64::engine::general_purpose::STANDARD
         .decode(signature_b64)
         .map_err(|e| ProvaraError::Encoding(format!("Base64 decode failed: {}", e)))?;
-    
+
     if sig_bytes.len() != 64 {
         return Err(ProvaraError::InvalidEvent(format!(
             "Invalid signature length: expected 64, got {}",
             sig_bytes.len()
         )));
     }
-    
-    let signature = Signature::from_slice(&sig_bytes)
-        .map_err(|e| ProvaraError::Crypto(format!("Invalid signature: {}", e)))?;
-    
-    // Parse public key
-    let verifying_key = VerifyingKey::from_bytes(public_key)
-        .map_err(|e| ProvaraError::Crypto(format!("Invalid public key: {}", e)))?;
-    
-    // Compute signing payload hash
+
+    // Compute signing payload hash (shared across suites)
     let signing_payload = event.signing_payload()?;
     let canonical_bytes = canonicalize(&signing_payload)?;
     let hash = sha256_hash(&canonical_bytes);
-    
-    // Verify signature
-    verifying_key.verify(&hash, &signature)
-        .map_err(|e| ProvaraError::Crypto(format!("Signature verification failed: {}", e)))?;
-    
+
+    match public_key {
+        PublicKeyBytes::Ed25519(key_bytes) => {
+            let signature = Signature::from_slice(&sig_bytes)
+                .map_err(|e| ProvaraError::Crypto(format!("Invalid signature: {}", e)))?;
+
+            let verifying_key = VerifyingKey::from_bytes(key_bytes)
+                .map_err(|e| ProvaraError::Crypto(format!("Invalid public key: {}", e)))?;
+
+            verifying_key.verify(&hash, &signature)
+                .map_err(|e| ProvaraError::Crypto(format!("Signature verification failed: {}", e)))?;
+        }
+        PublicKeyBytes::Es256k(key_bytes) => {
+            let signature = k256::ecdsa::Signature::from_slice(&sig_bytes)
+                .map_err(|e| ProvaraError::Crypto(format!("Invalid signature: {}", e)))?;
+
+            let verifying_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(key_bytes)
+                .map_err(|e| ProvaraError::Crypto(format!("Invalid public key: {}", e)))?;
+
+            verifying_key.verify_prehash(&hash, &signature)
+                .map_err(|e| ProvaraError::Crypto(format!("Signature verification failed: {}", e)))?;
+        }
+    }
+
     Ok(true)
 }
 
@@ -375,10 +660,124 @@ pub fn verify_causal_chain(events: &[Event]) -> Result<(), ProvaraError> {
         // Update last event for this actor
         actor_last_event.insert(actor.clone(), event.event_id.clone());
     }
-    
+
     Ok(())
 }
 
+/// A single causal-chain integrity problem found for one actor's events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainViolation {
+    pub actor: String,
+    pub event_id: String,
+    pub reason: String,
+}
+
+/// Verify causal chain integrity, additionally enforcing `sequence` where
+/// an actor's events carry one.
+///
+/// For any actor whose events carry a `sequence`, that actor's genesis
+/// event must be sequence 1 with `prev_event_hash == None`, and each
+/// subsequent event must both reference the previous event's id and carry
+/// `sequence == prev.sequence + 1`. Actors that never set `sequence` are
+/// checked on `prev_event_hash` linkage alone, same as `verify_causal_chain`.
+///
+/// When `check_timestamp_monotonic` is true, an event whose `timestamp_utc`
+/// sorts strictly before its predecessor's (same actor) is also reported.
+///
+/// Unlike `verify_causal_chain`, this does not stop at the first problem —
+/// it returns every violation found so callers can report all chain faults
+/// across all actors at once.
+pub fn verify_causal_chain_detailed(events: &[Event], check_timestamp_monotonic: bool) -> Vec<ChainViolation> {
+    use std::collections::BTreeMap;
+
+    struct LastSeen<'a> {
+        event_id: &'a str,
+        sequence: Option<u64>,
+        timestamp_utc: Option<&'a str>,
+    }
+
+    let mut violations = Vec::new();
+    let mut last_by_actor: BTreeMap<&str, LastSeen> = BTreeMap::new();
+
+    for event in events {
+        let actor = event.actor.as_str();
+
+        match last_by_actor.get(actor) {
+            None => {
+                if event.prev_event_hash.is_some() {
+                    violations.push(ChainViolation {
+                        actor: actor.to_string(),
+                        event_id: event.event_id.clone(),
+                        reason: "genesis event has a prev_event_hash".to_string(),
+                    });
+                }
+                if let Some(seq) = event.sequence {
+                    if seq != 1 {
+                        violations.push(ChainViolation {
+                            actor: actor.to_string(),
+                            event_id: event.event_id.clone(),
+                            reason: format!("genesis event has sequence {}, expected 1", seq),
+                        });
+                    }
+                }
+            }
+            Some(last) => {
+                match &event.prev_event_hash {
+                    Some(prev) if prev == last.event_id => {}
+                    Some(prev) => violations.push(ChainViolation {
+                        actor: actor.to_string(),
+                        event_id: event.event_id.clone(),
+                        reason: format!("prev_event_hash {} does not match expected {}", prev, last.event_id),
+                    }),
+                    None => violations.push(ChainViolation {
+                        actor: actor.to_string(),
+                        event_id: event.event_id.clone(),
+                        reason: "non-genesis event has no prev_event_hash".to_string(),
+                    }),
+                }
+
+                match (event.sequence, last.sequence) {
+                    (Some(seq), Some(last_seq)) if seq != last_seq + 1 => {
+                        violations.push(ChainViolation {
+                            actor: actor.to_string(),
+                            event_id: event.event_id.clone(),
+                            reason: format!("sequence {} does not follow {}", seq, last_seq),
+                        });
+                    }
+                    (Some(_), None) | (None, Some(_)) => {
+                        violations.push(ChainViolation {
+                            actor: actor.to_string(),
+                            event_id: event.event_id.clone(),
+                            reason: "actor mixes events with and without sequence numbers".to_string(),
+                        });
+                    }
+                    _ => {}
+                }
+
+                if check_timestamp_monotonic {
+                    if let (Some(ts), Some(last_ts)) = (event.timestamp_utc.as_deref(), last.timestamp_utc) {
+                        if ts < last_ts {
+                            violations.push(ChainViolation {
+                                actor: actor.to_string(),
+                                event_id: event.event_id.clone(),
+                                reason: format!("timestamp_utc {} precedes predecessor's {}", ts, last_ts),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        last_by_actor.insert(actor, LastSeen {
+            event_id: &event.event_id,
+            sequence: event.sequence,
+            timestamp_utc: event.timestamp_utc.as_deref(),
+        });
+    }
+
+    violations
+}
+
 /// Compute Merkle root from file entries
 ///
 /// File entries must be sorted lexicographically by path.
@@ -443,23 +842,23 @@ pub fn compute_state_hash(state: &Value) -> Result<String, ProvaraError> {
     Ok(hash)
 }
 
-/// Import a public key from Base64-encoded bytes
-pub fn import_public_key_b64(key_b64: &str) -> Result<[u8; 32], ProvaraError> {
+/// Import an Ed25519 public key from Base64-encoded bytes
+pub fn import_public_key_b64(key_b64: &str) -> Result<PublicKeyBytes, ProvaraError> {
     use base64::Engine as _;
     let bytes = base64::engine::general_purpose::STANDARD
         .decode(key_b64)
         .map_err(|e| ProvaraError::Encoding(format!("Base64 decode failed: {}", e)))?;
-    
+
     if bytes.len() != 32 {
         return Err(ProvaraError::KeyDerivation(format!(
             "Invalid public key length: expected 32 bytes, got {}",
             bytes.len()
         )));
     }
-    
+
     let mut key = [0u8; 32];
     key.copy_from_slice(&bytes);
-    Ok(key)
+    Ok(PublicKeyBytes::Ed25519(key))
 }
 
 // ---------------------------------------------------------------------------
@@ -491,7 +890,7 @@ impl WasmKeyPair {
     #[wasm_bindgen(getter)]
     pub fn public_key_b64(&self) -> String {
         use base64::Engine as _;
-        base64::engine::general_purpose::STANDARD.encode(self.inner.public_key())
+        base64::engine::general_purpose::STANDARD.encode(self.inner.public_key().as_bytes())
     }
 
     #[wasm_bindgen(getter)]
@@ -507,11 +906,17 @@ impl WasmKeyPair {
 #[wasm_bindgen]
 pub fn generate_keypair_js() -> Result<JsValue, JsValue> {
     use rand_core::OsRng;
-    use base64::Engine as _;
 
     let kp = KeyPair::generate(&mut OsRng);
+    keypair_to_js_object(&kp)
+}
+
+#[cfg(feature = "wasm")]
+fn keypair_to_js_object(kp: &KeyPair) -> Result<JsValue, JsValue> {
+    use base64::Engine as _;
+
     let key_id = kp.key_id().map_err(|e| JsValue::from_str(&e.to_string()))?;
-    let public_key_b64 = base64::engine::general_purpose::STANDARD.encode(kp.public_key());
+    let public_key_b64 = base64::engine::general_purpose::STANDARD.encode(kp.public_key().as_bytes());
     let private_key_b64 = base64::engine::general_purpose::STANDARD.encode(kp.seed_bytes());
 
     let obj = js_sys::Object::new();
@@ -522,6 +927,28 @@ pub fn generate_keypair_js() -> Result<JsValue, JsValue> {
     Ok(obj.into())
 }
 
+/// Derive an Ed25519 keypair from a passphrase ("brain wallet").
+/// Returns the same `{ key_id, public_key_b64, private_key_b64 }` shape as
+/// `generate_keypair_js`.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn keypair_from_passphrase_js(passphrase: &str) -> Result<JsValue, JsValue> {
+    let kp = KeyPair::from_passphrase(passphrase).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    keypair_to_js_object(&kp)
+}
+
+/// Mine a vanity key_id whose hex portion starts with `prefix` (lowercase
+/// hex, at most 8 characters), trying up to `max_attempts` random keypairs.
+/// Returns `null` if no match was found within the attempt budget.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn mine_vanity_key_js(prefix: &str, max_attempts: u64) -> Result<JsValue, JsValue> {
+    match KeyPair::mine_vanity(prefix, max_attempts) {
+        Some(kp) => keypair_to_js_object(&kp),
+        None => Ok(JsValue::NULL),
+    }
+}
+
 /// Create and sign a Provara event.
 ///
 /// - event_type: "OBSERVATION" | "ATTESTATION" | "RETRACTION" | …
@@ -639,11 +1066,43 @@ mod tests {
         let public_key_bytes = hex::decode(public_key_hex).unwrap();
         let mut key = [0u8; 32];
         key.copy_from_slice(&public_key_bytes);
-        
-        let key_id = derive_key_id(&key).unwrap();
+
+        let key_id = derive_key_id(&PublicKeyBytes::Ed25519(key)).unwrap();
         assert_eq!(key_id, "bp1_5c99599d178e7632");
     }
 
+    #[test]
+    fn test_es256k_sign_verify_round_trip() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate_with_algorithm(Algorithm::Es256k, &mut rng);
+
+        let event = create_event(
+            "OBSERVATION",
+            &keypair,
+            None,
+            json!({"subject": "test", "value": "ok"}),
+        ).unwrap();
+
+        assert_eq!(event.alg.as_deref(), Some("ES256K"));
+        assert!(verify_event_signature(&event, &keypair.public_key()).unwrap());
+    }
+
+    #[test]
+    fn test_alg_mismatch_rejected() {
+        let mut rng = thread_rng();
+        let ed25519_keypair = KeyPair::generate(&mut rng);
+        let es256k_keypair = KeyPair::generate_with_algorithm(Algorithm::Es256k, &mut rng);
+
+        let event = create_event(
+            "OBSERVATION",
+            &ed25519_keypair,
+            None,
+            json!({"subject": "test"}),
+        ).unwrap();
+
+        assert!(verify_event_signature(&event, &es256k_keypair.public_key()).is_err());
+    }
+
     #[test]
     fn test_event_creation() {
         let mut rng = thread_rng();
@@ -726,6 +1185,88 @@ mod tests {
         assert!(verify_causal_chain(&events).is_err());
     }
 
+    #[test]
+    fn test_sequence_enforced_when_present() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let mut event1 = create_event(
+            "OBSERVATION",
+            &keypair,
+            None,
+            json!({"seq": 1}),
+        ).unwrap();
+        event1.sequence = Some(1);
+
+        let mut event2 = create_event(
+            "OBSERVATION",
+            &keypair,
+            Some(event1.event_id.clone()),
+            json!({"seq": 2}),
+        ).unwrap();
+        // Skips a sequence number even though prev_event_hash links correctly.
+        event2.sequence = Some(3);
+
+        let violations = verify_causal_chain_detailed(&[event1, event2], false);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].reason.contains("does not follow"));
+    }
+
+    #[test]
+    fn test_timestamp_monotonicity_opt_in() {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let event1 = create_event_full(
+            "OBSERVATION",
+            &keypair,
+            None,
+            json!({"seq": 1}),
+            Some("2026-01-02T00:00:00Z".to_string()),
+        ).unwrap();
+
+        let event2 = create_event_full(
+            "OBSERVATION",
+            &keypair,
+            Some(event1.event_id.clone()),
+            json!({"seq": 2}),
+            Some("2026-01-01T00:00:00Z".to_string()),
+        ).unwrap();
+
+        let events = [event1, event2];
+        assert!(verify_causal_chain_detailed(&events, false).is_empty());
+
+        let violations = verify_causal_chain_detailed(&events, true);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].reason.contains("precedes predecessor"));
+    }
+
+    #[test]
+    fn test_passphrase_derivation_is_deterministic() {
+        let a = KeyPair::from_passphrase("correct horse battery staple").unwrap();
+        let b = KeyPair::from_passphrase("correct horse battery staple").unwrap();
+        assert_eq!(a.seed_bytes(), b.seed_bytes());
+
+        let c = KeyPair::from_passphrase("a different passphrase").unwrap();
+        assert_ne!(a.seed_bytes(), c.seed_bytes());
+    }
+
+    #[test]
+    fn test_vanity_mining_finds_trivial_prefix() {
+        // Empty isn't a valid prefix, but a single hex digit should be found
+        // quickly within a generous attempt budget.
+        let kp = KeyPair::mine_vanity("0", 10_000).expect("should find a match within budget");
+        let key_id = kp.key_id().unwrap();
+        assert!(key_id.strip_prefix("bp1_").unwrap().starts_with('0'));
+    }
+
+    #[test]
+    fn test_vanity_mining_rejects_invalid_prefix() {
+        assert!(KeyPair::mine_vanity("", 100).is_none());
+        assert!(KeyPair::mine_vanity("zz", 100).is_none());
+        assert!(KeyPair::mine_vanity("ABCDEFGHI", 100).is_none());
+    }
+
     #[test]
     fn test_merkle_root() {
         let entries = vec![
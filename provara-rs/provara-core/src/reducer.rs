@@ -2,6 +2,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, Map, json};
 use jcs_rs::{canonical_hash_hex, canonical_to_string};
+use crate::{verify_event_signature, Event, PublicKeyBytes};
 
 const REDUCER_NAME: &str = "SovereignReducerV0";
 const REDUCER_VERSION: &str = "0.2.0";
@@ -33,6 +34,14 @@ pub struct StateMetadata {
     pub state_hash: Option<String>,
     pub current_epoch: Option<Value>,
     pub reducer: ReducerMetadata,
+    /// Count of events accepted by `apply_events_verified`.
+    pub accepted_count: u64,
+    /// Count of events diverted to `ReducerState::rejected` by
+    /// `apply_events_verified`.
+    pub rejected_count: u64,
+    /// The last verified event_id accepted along each actor's chain, as
+    /// seen by `apply_events_verified`.
+    pub actor_chain_tips: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +50,9 @@ pub struct ReducerState {
     pub local: BTreeMap<String, Value>,
     pub contested: BTreeMap<String, Value>,
     pub archived: BTreeMap<String, Vec<Value>>,
+    /// Events `apply_events_verified` refused to apply, keyed by event_id,
+    /// each recording its failure reason alongside the original event.
+    pub rejected: BTreeMap<String, Value>,
     pub metadata: StateMetadata,
 }
 
@@ -60,6 +72,7 @@ impl SovereignReducerV0 {
                 local: BTreeMap::new(),
                 contested: BTreeMap::new(),
                 archived: BTreeMap::new(),
+                rejected: BTreeMap::new(),
                 metadata: StateMetadata {
                     last_event_id: None,
                     event_count: 0,
@@ -70,6 +83,9 @@ impl SovereignReducerV0 {
                         version: REDUCER_VERSION.to_string(),
                         conflict_confidence_threshold: threshold,
                     },
+                    accepted_count: 0,
+                    rejected_count: 0,
+                    actor_chain_tips: BTreeMap::new(),
                 },
             },
             evidence: BTreeMap::new(),
@@ -92,6 +108,92 @@ impl SovereignReducerV0 {
         self.update_state_hash();
     }
 
+    /// Apply `events`, verifying each one's signature and causal-chain
+    /// linkage against `key_map` (actor -> its public key) before mutating
+    /// state — the signature-verified counterpart of `apply_events`, reusing
+    /// the same canonical-JCS-then-SHA-256-then-signature check
+    /// `cross_impl::verify_vault` performs. An event that fails verification
+    /// is diverted into `ReducerState::rejected` with its failure reason
+    /// instead of being applied; `ignored_types` behavior is unchanged for
+    /// unknown but otherwise valid event types.
+    pub fn apply_events_verified(&mut self, events: &[Value], key_map: &BTreeMap<String, PublicKeyBytes>) {
+        for event in events {
+            self.apply_event_verified_internal(event, key_map);
+        }
+        self.update_state_hash();
+    }
+
+    fn reject(&mut self, event_id: &str, event: &Value, reason: &str) {
+        self.state.rejected.insert(
+            event_id.to_string(),
+            json!({"reason": reason, "event": event}),
+        );
+        self.state.metadata.rejected_count += 1;
+    }
+
+    fn apply_event_verified_internal(&mut self, event: &Value, key_map: &BTreeMap<String, PublicKeyBytes>) {
+        if event.as_object().is_none() {
+            return;
+        }
+
+        let event_id = event
+            .get("event_id")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown_event")
+            .to_string();
+
+        let parsed: Event = match serde_json::from_value(event.clone()) {
+            Ok(e) => e,
+            Err(err) => {
+                self.reject(&event_id, event, &format!("malformed event: {err}"));
+                return;
+            }
+        };
+
+        let tip = self.state.metadata.actor_chain_tips.get(&parsed.actor).cloned();
+        match (&parsed.prev_event_hash, &tip) {
+            (None, None) => {}
+            (None, Some(_)) => {
+                self.reject(&event_id, event, &format!("actor {} has multiple genesis events", parsed.actor));
+                return;
+            }
+            (Some(prev), Some(expected)) if prev == expected => {}
+            (Some(prev), Some(expected)) => {
+                self.reject(
+                    &event_id,
+                    event,
+                    &format!("broken chain for actor {}: expected prev {expected}, got {prev}", parsed.actor),
+                );
+                return;
+            }
+            (Some(_), None) => {
+                self.reject(&event_id, event, &format!("actor {} references non-existent previous event", parsed.actor));
+                return;
+            }
+        }
+
+        let Some(public_key) = key_map.get(&parsed.actor) else {
+            self.reject(&event_id, event, &format!("no known public key for actor {}", parsed.actor));
+            return;
+        };
+
+        match verify_event_signature(&parsed, public_key) {
+            Ok(true) => {}
+            Ok(false) => {
+                self.reject(&event_id, event, "signature verification returned false");
+                return;
+            }
+            Err(err) => {
+                self.reject(&event_id, event, &format!("signature verification failed: {err}"));
+                return;
+            }
+        }
+
+        self.state.metadata.actor_chain_tips.insert(parsed.actor.clone(), event_id);
+        self.state.metadata.accepted_count += 1;
+        self.apply_event_internal(event);
+    }
+
     fn apply_event_internal(&mut self, event: &Value) {
         let obj = match event.as_object() {
             Some(o) => o,
@@ -333,11 +435,15 @@ impl SovereignReducerV0 {
             "local": self.state.local,
             "contested": self.state.contested,
             "archived": self.state.archived,
+            "rejected": self.state.rejected,
             "metadata_partial": {
                 "last_event_id": self.state.metadata.last_event_id,
                 "event_count": self.state.metadata.event_count,
                 "current_epoch": self.state.metadata.current_epoch,
                 "reducer": self.state.metadata.reducer,
+                "accepted_count": self.state.metadata.accepted_count,
+                "rejected_count": self.state.metadata.rejected_count,
+                "actor_chain_tips": self.state.metadata.actor_chain_tips,
             },
         });
         
@@ -380,4 +486,76 @@ mod tests {
         assert_eq!(reducer.state.local.get("door:status").unwrap()["value"], "open");
         assert_eq!(reducer.state.metadata.event_count, 1);
     }
+
+    #[test]
+    fn test_apply_events_verified_accepts_valid_chain() {
+        use crate::{create_event, KeyPair};
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+
+        let event1 = create_event(
+            "OBSERVATION",
+            &keypair,
+            None,
+            json!({"subject": "door", "predicate": "status", "value": "open", "confidence": 0.9}),
+        ).unwrap();
+        let event2 = create_event(
+            "OBSERVATION",
+            &keypair,
+            Some(event1.event_id.clone()),
+            json!({"subject": "door", "predicate": "status", "value": "closed", "confidence": 0.95}),
+        ).unwrap();
+
+        let mut key_map = BTreeMap::new();
+        key_map.insert(event1.actor.clone(), keypair.public_key());
+
+        let mut reducer = SovereignReducerV0::new(None);
+        let events = vec![
+            serde_json::to_value(&event1).unwrap(),
+            serde_json::to_value(&event2).unwrap(),
+        ];
+        reducer.apply_events_verified(&events, &key_map);
+
+        assert_eq!(reducer.state.metadata.accepted_count, 2);
+        assert_eq!(reducer.state.metadata.rejected_count, 0);
+        assert!(reducer.state.rejected.is_empty());
+        assert_eq!(
+            reducer.state.metadata.actor_chain_tips.get(&event1.actor),
+            Some(&event2.event_id)
+        );
+        assert_eq!(reducer.state.local.get("door:status").unwrap()["value"], "closed");
+    }
+
+    #[test]
+    fn test_apply_events_verified_rejects_unsigned_tamper() {
+        use crate::{create_event, KeyPair};
+        use rand::thread_rng;
+
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+        let mut event = create_event(
+            "OBSERVATION",
+            &keypair,
+            None,
+            json!({"subject": "door", "predicate": "status", "value": "open", "confidence": 0.9}),
+        ).unwrap();
+
+        let event_id = event.event_id.clone();
+        let actor = event.actor.clone();
+        event.payload = json!({"subject": "door", "predicate": "status", "value": "tampered", "confidence": 0.9});
+
+        let mut key_map = BTreeMap::new();
+        key_map.insert(actor, keypair.public_key());
+
+        let mut reducer = SovereignReducerV0::new(None);
+        let events = vec![serde_json::to_value(&event).unwrap()];
+        reducer.apply_events_verified(&events, &key_map);
+
+        assert_eq!(reducer.state.metadata.accepted_count, 0);
+        assert_eq!(reducer.state.metadata.rejected_count, 1);
+        assert!(reducer.state.rejected.contains_key(&event_id));
+        assert!(reducer.state.local.is_empty());
+    }
 }
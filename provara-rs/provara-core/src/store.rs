@@ -0,0 +1,185 @@
+//! Pull-based event store abstraction for streaming backward verification.
+//!
+//! `verify_causal_chain`/`verify_causal_chain_detailed` both require the
+//! full actor history in a `Vec<Event>`. `EventStore` lets a caller verify a
+//! single actor's chain one hop at a time, fetching only the events it
+//! actually visits — useful when the chain is backed by a database or log
+//! far larger than fits in memory.
+
+use crate::{derive_event_id, verify_event_signature, Event, ProvaraError, PublicKeyBytes};
+use std::collections::BTreeMap;
+
+/// A source of events addressable by `event_id`.
+pub trait EventStore {
+    fn get_event(&self, event_id: &str) -> Result<Option<Event>, ProvaraError>;
+}
+
+/// A simple in-memory `EventStore`, primarily useful for tests and small
+/// tools that don't warrant a real backing store.
+#[derive(Debug, Default, Clone)]
+pub struct InMemoryEventStore {
+    events: BTreeMap<String, Event>,
+}
+
+impl InMemoryEventStore {
+    pub fn new() -> Self {
+        Self { events: BTreeMap::new() }
+    }
+
+    pub fn insert(&mut self, event: Event) {
+        self.events.insert(event.event_id.clone(), event);
+    }
+}
+
+impl EventStore for InMemoryEventStore {
+    fn get_event(&self, event_id: &str) -> Result<Option<Event>, ProvaraError> {
+        Ok(self.events.get(event_id).cloned())
+    }
+}
+
+/// Verify an actor's causal chain by walking backward from `head_id`,
+/// fetching one event at a time from `store` rather than requiring the
+/// whole chain up front.
+///
+/// Verifies the head event's signature (when `known_pubkeys` resolves its
+/// actor to an Ed25519 public key), then confirms `derive_event_id` on the
+/// head reproduces its own stored `event_id` — catching a tampered or
+/// corrupted head before it is trusted. From there it follows
+/// `prev_event_hash` backward one hop at a time: each predecessor is fetched
+/// from `store`, and `derive_event_id` is recomputed on it to confirm that
+/// id equals the child's `prev_event_hash`, so a stored event whose id
+/// doesn't match its own content is caught rather than silently trusted.
+/// Walking stops at the genesis event (`prev_event_hash == None`).
+///
+/// Returns the first broken link found, naming the offending event id.
+pub fn verify_chain_from<S: EventStore>(
+    store: &S,
+    head_id: &str,
+    known_pubkeys: &dyn Fn(&str) -> Option<[u8; 32]>,
+) -> Result<(), ProvaraError> {
+    let head = store
+        .get_event(head_id)?
+        .ok_or_else(|| ProvaraError::ChainValidation(format!("event {} not found in store", head_id)))?;
+
+    if let Some(seed) = known_pubkeys(&head.actor) {
+        let public_key = PublicKeyBytes::Ed25519(seed);
+        if !verify_event_signature(&head, &public_key)? {
+            return Err(ProvaraError::ChainValidation(format!(
+                "head event {} has an invalid signature",
+                head_id
+            )));
+        }
+    }
+
+    let recomputed_head_id = derive_event_id(&head)?;
+    if recomputed_head_id != head.event_id || head.event_id != head_id {
+        return Err(ProvaraError::ChainValidation(format!(
+            "head event {} does not match its own content (recomputes to {})",
+            head_id, recomputed_head_id
+        )));
+    }
+
+    let mut child = head;
+    loop {
+        let prev_id = match &child.prev_event_hash {
+            None => return Ok(()),
+            Some(prev_id) => prev_id.clone(),
+        };
+
+        let prev = store.get_event(&prev_id)?.ok_or_else(|| {
+            ProvaraError::ChainValidation(format!(
+                "event {} references missing predecessor {}",
+                child.event_id, prev_id
+            ))
+        })?;
+
+        let recomputed_prev_id = derive_event_id(&prev)?;
+        if recomputed_prev_id != prev_id {
+            return Err(ProvaraError::ChainValidation(format!(
+                "event {} claims predecessor {}, but that id does not match the predecessor's content (recomputes to {})",
+                child.event_id, prev_id, recomputed_prev_id
+            )));
+        }
+
+        child = prev;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_event, KeyPair};
+    use rand::thread_rng;
+    use serde_json::json;
+
+    fn build_chain(len: usize) -> (InMemoryEventStore, KeyPair, String) {
+        let mut rng = thread_rng();
+        let keypair = KeyPair::generate(&mut rng);
+        let mut store = InMemoryEventStore::new();
+
+        let mut prev_id = None;
+        let mut head_id = String::new();
+        for i in 0..len {
+            let event = create_event("OBSERVATION", &keypair, prev_id.clone(), json!({"i": i})).unwrap();
+            head_id = event.event_id.clone();
+            prev_id = Some(event.event_id.clone());
+            store.insert(event);
+        }
+
+        (store, keypair, head_id)
+    }
+
+    #[test]
+    fn test_verify_chain_from_accepts_valid_chain() {
+        let (store, keypair, head_id) = build_chain(5);
+        let PublicKeyBytes::Ed25519(seed) = keypair.public_key() else {
+            panic!("expected Ed25519 key");
+        };
+        let known = move |_actor: &str| Some(seed);
+
+        assert!(verify_chain_from(&store, &head_id, &known).is_ok());
+    }
+
+    #[test]
+    fn test_verify_chain_from_detects_tampered_predecessor() {
+        let (mut store, keypair, head_id) = build_chain(3);
+        let PublicKeyBytes::Ed25519(seed) = keypair.public_key() else {
+            panic!("expected Ed25519 key");
+        };
+        let known = move |_actor: &str| Some(seed);
+
+        // Corrupt a non-head event's payload in place, without re-deriving
+        // its event_id, so the stored id no longer matches its content.
+        let head = store.get_event(&head_id).unwrap().unwrap();
+        let prev_id = head.prev_event_hash.clone().unwrap();
+        let mut tampered = store.get_event(&prev_id).unwrap().unwrap();
+        tampered.payload = json!({"i": "tampered"});
+        store.insert(tampered);
+
+        let err = verify_chain_from(&store, &head_id, &known).unwrap_err();
+        assert!(err.to_string().contains(&prev_id));
+    }
+
+    #[test]
+    fn test_verify_chain_from_detects_missing_predecessor() {
+        let (store, keypair, head_id) = build_chain(1);
+        let PublicKeyBytes::Ed25519(seed) = keypair.public_key() else {
+            panic!("expected Ed25519 key");
+        };
+        let known = move |_actor: &str| Some(seed);
+
+        // head_id has no predecessor, so a lone genesis event verifies fine.
+        assert!(verify_chain_from(&store, &head_id, &known).is_ok());
+
+        let (mut store2, keypair2, head_id2) = build_chain(2);
+        let PublicKeyBytes::Ed25519(seed2) = keypair2.public_key() else {
+            panic!("expected Ed25519 key");
+        };
+        let known2 = move |_actor: &str| Some(seed2);
+        let head2 = store2.get_event(&head_id2).unwrap().unwrap();
+        let prev_id2 = head2.prev_event_hash.clone().unwrap();
+        store2.events.remove(&prev_id2);
+
+        assert!(verify_chain_from(&store2, &head_id2, &known2).is_err());
+    }
+}
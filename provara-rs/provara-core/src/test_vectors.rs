@@ -6,7 +6,7 @@
 use crate::{
     canonical_to_string, compute_merkle_root,
     create_event, derive_key_id, derive_event_id, import_public_key_b64,
-    verify_event_signature, Event, KeyPair, sha256_hash_hex,
+    verify_event_signature, Event, KeyPair, PublicKeyBytes, sha256_hash_hex,
 };
 use serde::Deserialize;
 use serde_json::Value;
@@ -97,8 +97,10 @@ fn test_event_id_derivation_01(input: &Value, expected: &Value) -> Result<(), St
         event_id: String::new(), // Will be derived
         actor: input["actor"].as_str().unwrap_or("").to_string(),
         prev_event_hash: input["prev_event_hash"].as_str().map(String::from),
-        payload: input["payload"].clone(),
         timestamp_utc: None,
+        alg: None,
+        sequence: None,
+        payload: input["payload"].clone(),
         signature: None,
     };
     
@@ -128,7 +130,7 @@ fn test_key_id_derivation_01(input: &Value, expected: &Value) -> Result<(), Stri
     let mut key = [0u8; 32];
     key.copy_from_slice(&public_key_bytes);
     
-    let key_id = derive_key_id(&key)
+    let key_id = derive_key_id(&PublicKeyBytes::Ed25519(key))
         .map_err(|e| format!("Key ID derivation failed: {}", e))?;
     
     let expected_id = expected.as_str()